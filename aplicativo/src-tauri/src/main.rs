@@ -1,14 +1,16 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use std::path::{Path, PathBuf};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 use serde::Serialize;
 use tauri::{Emitter, Manager, AppHandle};
 use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, MenuEvent};
 use tauri::tray::TrayIcon;
 use tauri::tray::{TrayIconBuilder, TrayIconEvent};
 use sysinfo::System;
+use tauri_plugin_dialog::DialogExt;
 
 #[tauri::command]
 fn validate_exe_path(path: String) -> bool {
@@ -39,7 +41,10 @@ fn is_process_running(process_name: String) -> Result<bool, String> {
   let output = std::process::Command::new("tasklist")
     .args(["/FI", &filter])
     .output()
-    .map_err(|error| error.to_string())?;
+    .map_err(|error| {
+      log_tool_error("tasklist", &error);
+      error.to_string()
+    })?;
 
   let stdout = String::from_utf8_lossy(&output.stdout).to_lowercase();
   Ok(stdout.contains(&name.to_lowercase()))
@@ -99,8 +104,8 @@ fn detect_path(binary: &str, folder: &str, exe: &str) -> Option<String> {
     return Some(found);
   }
 
-  if let Ok(output) = std::process::Command::new("where").arg(binary).output() {
-    if output.status.success() {
+  match std::process::Command::new("where").arg(binary).output() {
+    Ok(output) if output.status.success() => {
       let stdout = String::from_utf8_lossy(&output.stdout);
       if let Some(line) = stdout.lines().next() {
         let trimmed = line.trim().trim_matches('"');
@@ -109,11 +114,48 @@ fn detect_path(binary: &str, folder: &str, exe: &str) -> Option<String> {
         }
       }
     }
+    Ok(_) => {}
+    Err(error) => log_tool_error("where", &error),
   }
 
   None
 }
 
+// release builds have no console, so the last failure per tool is also kept
+// for get_tool_errors and the diagnostics report
+static TOOL_ERRORS: OnceLock<Mutex<BTreeMap<String, (String, String)>>> = OnceLock::new();
+
+fn tool_errors() -> &'static Mutex<BTreeMap<String, (String, String)>> {
+  TOOL_ERRORS.get_or_init(|| Mutex::new(BTreeMap::new()))
+}
+
+fn log_tool_error(tool: &str, error: &std::io::Error) {
+  let (status, message) = if error.kind() == std::io::ErrorKind::NotFound {
+    ("missing", format!("{} nao encontrado no PATH", tool))
+  } else {
+    ("error", format!("falha ao executar {}: {}", tool, error))
+  };
+  eprintln!("[tools] {}", message);
+  if let Ok(mut guard) = tool_errors().lock() {
+    guard.insert(tool.to_string(), (status.to_string(), message));
+  }
+}
+
+#[tauri::command]
+fn get_tool_errors() -> Vec<ToolStatus> {
+  if let Ok(guard) = tool_errors().lock() {
+    return guard
+      .iter()
+      .map(|(tool, (status, message))| ToolStatus {
+        name: tool.clone(),
+        status: status.clone(),
+        detail: Some(message.clone()),
+      })
+      .collect();
+  }
+  Vec::new()
+}
+
 fn first_existing(paths: &[PathBuf]) -> Option<String> {
   for path in paths {
     if path.is_file() {
@@ -163,11 +205,18 @@ struct CommandOutput {
 #[allow(non_snake_case)]
 struct HardwareProfile {
   cpuName: String,
+  cpuPhysicalCores: Option<u32>,
+  cpuLogicalThreads: Option<u32>,
   ramGb: u64,
   gpuName: String,
+  gpus: Vec<String>,
+  vramMb: Option<u64>,
   storageSummary: String,
+  drives: Vec<DriveInfo>,
   osName: Option<String>,
   screenResolution: Option<String>,
+  refreshHz: Option<u32>,
+  hdrCapable: Option<bool>,
 }
 
 #[derive(Serialize, Clone)]
@@ -177,34 +226,135 @@ struct HardwareProgress {
   status: String,
 }
 
-static HARDWARE_CANCEL: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+const EVENT_HARDWARE_PROGRESS: &str = "hardware-progress";
+const EVENT_HARDWARE_PROFILE_READY: &str = "hardware-profile-ready";
+const EVENT_TRAY_ACTION: &str = "tray-action";
+
+// every event the backend emits: (name, description, payload shape)
+const BACKEND_EVENTS: [(&str, &str, &str); 3] = [
+  (
+    EVENT_HARDWARE_PROGRESS,
+    "Progresso da deteccao de hardware",
+    "{ requestId: string, status: string }",
+  ),
+  (
+    EVENT_HARDWARE_PROFILE_READY,
+    "Resultado final da deteccao de hardware",
+    "{ requestId: string, profile: HardwareProfile | null, error: string | null }",
+  ),
+  (
+    EVENT_TRAY_ACTION,
+    "Acao escolhida no menu da bandeja",
+    "string (\"end_session\")",
+  ),
+];
+
+const MAX_PENDING_CANCELS: usize = 64;
 
-fn cancel_set() -> &'static Mutex<HashSet<String>> {
-  HARDWARE_CANCEL.get_or_init(|| Mutex::new(HashSet::new()))
+// kept in insertion order so the oldest ids are evicted first
+static HARDWARE_CANCEL: OnceLock<Mutex<VecDeque<String>>> = OnceLock::new();
+
+fn cancel_set() -> &'static Mutex<VecDeque<String>> {
+  HARDWARE_CANCEL.get_or_init(|| Mutex::new(VecDeque::new()))
 }
 
 fn set_cancel(request_id: &str) {
   if let Ok(mut guard) = cancel_set().lock() {
-    guard.insert(request_id.to_string());
+    if guard.iter().any(|id| id == request_id) {
+      return;
+    }
+    guard.push_back(request_id.to_string());
+    while guard.len() > MAX_PENDING_CANCELS {
+      guard.pop_front();
+    }
   }
 }
 
 fn clear_cancel(request_id: &str) {
   if let Ok(mut guard) = cancel_set().lock() {
-    guard.remove(request_id);
+    guard.retain(|id| id != request_id);
   }
 }
 
 fn is_cancelled(request_id: &str) -> bool {
   if let Ok(guard) = cancel_set().lock() {
-    return guard.contains(request_id);
+    return guard.iter().any(|id| id == request_id);
   }
   false
 }
 
+const HARDWARE_PROFILE_TTL_SECS: u64 = 60;
+
+// the detection steps sit behind this trait so the cache can be exercised
+// without wmic or a running app
+trait HardwareProbe {
+  fn detect(&self) -> Result<HardwareProfile, String>;
+}
+
+struct SystemHardwareProbe<'a> {
+  app: &'a tauri::AppHandle,
+  request_id: &'a str,
+}
+
+impl HardwareProbe for SystemHardwareProbe<'_> {
+  fn detect(&self) -> Result<HardwareProfile, String> {
+    detect_hardware_profile(self.app, self.request_id)
+  }
+}
+
+struct HardwareProfileCache {
+  entry: Mutex<Option<(Instant, HardwareProfile)>>,
+}
+
+impl HardwareProfileCache {
+  fn new() -> Self {
+    HardwareProfileCache {
+      entry: Mutex::new(None),
+    }
+  }
+
+  fn latest(&self) -> Option<HardwareProfile> {
+    if let Ok(guard) = self.entry.lock() {
+      return guard.as_ref().map(|(_, profile)| profile.clone());
+    }
+    None
+  }
+
+  fn fresh(&self, ttl: Duration) -> Option<HardwareProfile> {
+    if let Ok(guard) = self.entry.lock() {
+      if let Some((stored_at, profile)) = guard.as_ref() {
+        if stored_at.elapsed() < ttl {
+          return Some(profile.clone());
+        }
+      }
+    }
+    None
+  }
+
+  // failed or cancelled detections leave the previous entry in place
+  fn get_or_detect(&self, force: bool, ttl: Duration, probe: &dyn HardwareProbe) -> Result<HardwareProfile, String> {
+    if !force {
+      if let Some(profile) = self.fresh(ttl) {
+        return Ok(profile);
+      }
+    }
+    let profile = probe.detect()?;
+    if let Ok(mut guard) = self.entry.lock() {
+      *guard = Some((Instant::now(), profile.clone()));
+    }
+    Ok(profile)
+  }
+}
+
+static HARDWARE_PROFILE_CACHE: OnceLock<HardwareProfileCache> = OnceLock::new();
+
+fn hardware_profile_cache() -> &'static HardwareProfileCache {
+  HARDWARE_PROFILE_CACHE.get_or_init(HardwareProfileCache::new)
+}
+
 fn emit_progress(app: &tauri::AppHandle, request_id: &str, status: &str) {
   let _ = app.emit(
-    "hardware-progress",
+    EVENT_HARDWARE_PROGRESS,
     HardwareProgress {
       requestId: request_id.to_string(),
       status: status.to_string(),
@@ -212,6 +362,29 @@ fn emit_progress(app: &tauri::AppHandle, request_id: &str, status: &str) {
   );
 }
 
+#[derive(Serialize, Clone)]
+#[allow(non_snake_case)]
+struct HardwareProfileReady {
+  requestId: String,
+  profile: Option<HardwareProfile>,
+  error: Option<String>,
+}
+
+fn emit_profile_ready(app: &tauri::AppHandle, request_id: &str, result: Result<HardwareProfile, String>) {
+  let (profile, error) = match result {
+    Ok(profile) => (Some(profile), None),
+    Err(error) => (None, Some(error)),
+  };
+  let _ = app.emit(
+    EVENT_HARDWARE_PROFILE_READY,
+    HardwareProfileReady {
+      requestId: request_id.to_string(),
+      profile,
+      error,
+    },
+  );
+}
+
 fn fnv1a_hash(input: &str) -> String {
   let mut hash: u64 = 0xcbf29ce484222325;
   for byte in input.as_bytes() {
@@ -221,35 +394,218 @@ fn fnv1a_hash(input: &str) -> String {
   format!("{:016x}", hash)
 }
 
+fn sha256_hash(input: &str) -> String {
+  use sha2::{Digest, Sha256};
+  Sha256::digest(input.as_bytes())
+    .iter()
+    .map(|byte| format!("{:02x}", byte))
+    .collect()
+}
+
+fn hash_pc_id(base: &str, algorithm: Option<&str>) -> Result<String, String> {
+  match algorithm.map(|value| value.trim().to_lowercase()).as_deref() {
+    None | Some("") | Some("fnv1a") => Ok(fnv1a_hash(base)),
+    Some("sha256") => Ok(sha256_hash(base)),
+    Some(other) => Err(format!("algoritmo nao suportado: {}", other)),
+  }
+}
+
+// wmic pads columns with spaces and ends rows with \r\r\n; keep the
+// non-empty, trimmed rows
+fn query_output_lines(stdout: &str) -> Vec<String> {
+  stdout
+    .lines()
+    .map(|line| line.trim().to_string())
+    .filter(|line| !line.is_empty())
+    .collect()
+}
+
 fn parse_wmic_lines(args: &[&str]) -> Vec<String> {
-  if let Ok(output) = std::process::Command::new("wmic").args(args).output() {
-    if output.status.success() {
-      let stdout = String::from_utf8_lossy(&output.stdout);
-      return stdout
-        .lines()
-        .map(|line| line.trim().to_string())
-        .filter(|line| !line.is_empty())
-        .collect();
+  match std::process::Command::new("wmic").args(args).output() {
+    Ok(output) if output.status.success() => {
+      return query_output_lines(&String::from_utf8_lossy(&output.stdout));
+    }
+    Ok(_) => {}
+    Err(error) => log_tool_error("wmic", &error),
+  }
+  Vec::new()
+}
+
+// CIM rows are joined with the same double-space padding wmic uses between
+// columns, so both outputs go through the same column extraction below
+fn parse_cim_lines(class: &str, properties: &[&str]) -> Vec<String> {
+  let columns: Vec<String> = properties
+    .iter()
+    .map(|property| format!("$_.{}", property))
+    .collect();
+  let script = format!(
+    "Get-CimInstance -ClassName {} | ForEach-Object {{ @({}) -join '  ' }}",
+    class,
+    columns.join(", ")
+  );
+  match std::process::Command::new("powershell")
+    .args(["-NoProfile", "-Command", &script])
+    .output()
+  {
+    Ok(output) if output.status.success() => {
+      return query_output_lines(&String::from_utf8_lossy(&output.stdout));
     }
+    Ok(_) => {}
+    Err(error) => log_tool_error("powershell", &error),
   }
   Vec::new()
 }
 
+// wmic was removed from recent Windows 11 builds; fall back to CIM when it
+// is missing or returns nothing
+fn query_wmi_lines(wmic_args: &[&str], cim_class: &str, cim_properties: &[&str]) -> Vec<String> {
+  let lines = parse_wmic_lines(wmic_args);
+  if !lines.is_empty() {
+    return lines;
+  }
+  parse_cim_lines(cim_class, cim_properties)
+}
+
+fn first_wmi_value(lines: &[String], header: &str) -> Option<String> {
+  lines
+    .iter()
+    .find(|line| !line.is_empty() && !line.to_lowercase().contains(header))
+    .cloned()
+}
+
+fn detect_gpu_names() -> Vec<String> {
+  let lines = query_wmi_lines(
+    &["path", "win32_VideoController", "get", "name"],
+    "Win32_VideoController",
+    &["Name"],
+  );
+  gpu_names_from_lines(lines)
+}
+
+// drops the wmic "Name" header; CIM output has no header row
+fn gpu_names_from_lines(lines: Vec<String>) -> Vec<String> {
+  lines
+    .into_iter()
+    .filter(|line| !line.trim().is_empty() && !line.to_lowercase().contains("name"))
+    .collect()
+}
+
+// dedicated NVIDIA/AMD cards beat integrated graphics; virtual display
+// adapters (remote desktop, streaming drivers) rank last
+fn gpu_vendor_score(name: &str) -> i32 {
+  let lower = name.to_lowercase();
+  if ["microsoft basic", "virtual", "remote", "parsec", "mirage"]
+    .iter()
+    .any(|keyword| lower.contains(keyword))
+  {
+    return -1;
+  }
+  if ["nvidia", "geforce", "quadro", "rtx"]
+    .iter()
+    .any(|keyword| lower.contains(keyword))
+  {
+    return 3;
+  }
+  if lower.contains("radeon rx") || lower.contains("radeon pro") || lower.contains(" rx ") {
+    return 3;
+  }
+  if lower.contains("intel") && lower.contains("arc") {
+    return 2;
+  }
+  if lower.contains("radeon") || lower.contains("amd") || lower.contains("intel") {
+    return 1;
+  }
+  0
+}
+
+fn pick_best_gpu(names: &[String]) -> Option<String> {
+  let mut best: Option<&String> = None;
+  let mut best_score = i32::MIN;
+  for name in names {
+    let score = gpu_vendor_score(name);
+    if score > best_score {
+      best_score = score;
+      best = Some(name);
+    }
+  }
+  best.cloned()
+}
+
 fn detect_gpu_name() -> String {
-  let lines = parse_wmic_lines(&["path", "win32_VideoController", "get", "name"]);
-  for line in lines {
-    if line.to_lowercase().contains("name") {
-      continue;
+  pick_best_gpu(&detect_gpu_names()).unwrap_or_else(|| "GPU desconhecida".to_string())
+}
+
+#[cfg(windows)]
+fn detect_dxgi_vram_mb() -> Option<u64> {
+  use windows::Win32::Graphics::Dxgi::{CreateDXGIFactory1, IDXGIFactory1, DXGI_ADAPTER_FLAG_SOFTWARE};
+  unsafe {
+    let factory: IDXGIFactory1 = CreateDXGIFactory1().ok()?;
+    let mut best: u64 = 0;
+    let mut index: u32 = 0;
+    while let Ok(adapter) = factory.EnumAdapters1(index) {
+      index += 1;
+      let Ok(desc) = adapter.GetDesc1() else {
+        continue;
+      };
+      if desc.Flags & (DXGI_ADAPTER_FLAG_SOFTWARE.0 as u32) != 0 {
+        continue;
+      }
+      best = best.max(desc.DedicatedVideoMemory as u64);
     }
-    if !line.is_empty() {
-      return line;
+    if best == 0 {
+      None
+    } else {
+      Some(best / 1024 / 1024)
     }
   }
-  "GPU desconhecida".to_string()
+}
+
+#[cfg(not(windows))]
+fn detect_dxgi_vram_mb() -> Option<u64> {
+  None
+}
+
+fn parse_adapter_ram_mb(lines: &[String]) -> Option<u64> {
+  lines
+    .iter()
+    .filter_map(|line| line.trim().parse::<u64>().ok())
+    .max()
+    .filter(|bytes| *bytes > 0)
+    .map(|bytes| bytes / 1024 / 1024)
+}
+
+// AdapterRAM is a 32-bit field that wraps above 4GB, so it is only used when
+// DXGI can't report DedicatedVideoMemory
+fn detect_vram_mb() -> Option<u64> {
+  detect_dxgi_vram_mb().or_else(|| {
+    let lines = query_wmi_lines(
+      &["path", "win32_VideoController", "get", "AdapterRAM"],
+      "Win32_VideoController",
+      &["AdapterRAM"],
+    );
+    parse_adapter_ram_mb(&lines)
+  })
 }
 
 fn detect_storage_summary() -> String {
-  let lines = parse_wmic_lines(&["diskdrive", "get", "MediaType,Size"]);
+  let lines = query_wmi_lines(
+    &["diskdrive", "get", "MediaType,Size"],
+    "Win32_DiskDrive",
+    &["MediaType", "Size"],
+  );
+  summarize_storage(&lines)
+}
+
+// wmic and CIM disagree on the MediaType/Size column order, so the size is
+// taken as the last numeric token rather than by position
+fn parse_size_bytes(line: &str) -> Option<u64> {
+  line
+    .split_whitespace()
+    .rev()
+    .find_map(|part| part.parse::<u64>().ok())
+}
+
+fn summarize_storage(lines: &[String]) -> String {
   let mut total_bytes: u64 = 0;
   let mut has_ssd = false;
   for line in lines {
@@ -260,11 +616,7 @@ fn detect_storage_summary() -> String {
     if lower.contains("ssd") || lower.contains("solid state") {
       has_ssd = true;
     }
-    let size = line
-      .split_whitespace()
-      .rev()
-      .find_map(|part| part.parse::<u64>().ok());
-    if let Some(bytes) = size {
+    if let Some(bytes) = parse_size_bytes(line) {
       total_bytes = total_bytes.saturating_add(bytes);
     }
   }
@@ -272,7 +624,7 @@ fn detect_storage_summary() -> String {
   if total_bytes == 0 {
     return "Disco".to_string();
   }
-  let total_gb = (total_bytes as f64 / 1024.0 / 1024.0 / 1024.0).round() as u64;
+  let total_gb = bytes_to_gb(total_bytes);
   let label = if has_ssd { "SSD" } else { "HDD" };
   if total_gb >= 1024 {
     let tb = (total_gb as f64 / 1024.0).round() as u64;
@@ -282,6 +634,234 @@ fn detect_storage_summary() -> String {
   }
 }
 
+#[derive(Serialize, Clone)]
+#[allow(non_snake_case)]
+struct DriveInfo {
+  model: String,
+  mediaType: Option<String>,
+  busType: Option<String>,
+  sizeGb: u64,
+  freeGb: Option<u64>,
+}
+
+// Win32_DiskDrive reports "Fixed hard disk media" for SSDs and HDDs alike;
+// MSFT_PhysicalDisk tells them apart (SSD/HDD) and adds the bus (NVMe/SATA/USB).
+// free space is summed over the volumes on each disk
+const DRIVE_QUERY_SCRIPT: &str = "Get-PhysicalDisk | ForEach-Object { \
+  $free = (Get-Partition -DiskNumber $_.DeviceId -ErrorAction SilentlyContinue \
+    | Get-Volume -ErrorAction SilentlyContinue \
+    | Measure-Object -Property SizeRemaining -Sum).Sum; \
+  [pscustomobject]@{ Model = $_.FriendlyName; MediaType = $_.MediaType; BusType = $_.BusType; \
+    Size = $_.Size; FreeSpace = $free } \
+} | ConvertTo-Csv -NoTypeInformation";
+
+fn bytes_to_gb(bytes: u64) -> u64 {
+  (bytes as f64 / 1024.0 / 1024.0 / 1024.0).round() as u64
+}
+
+fn split_csv_line(line: &str) -> Vec<String> {
+  let mut fields = Vec::new();
+  let mut current = String::new();
+  let mut quoted = false;
+  let mut chars = line.chars().peekable();
+  while let Some(ch) = chars.next() {
+    match ch {
+      '"' if quoted && chars.peek() == Some(&'"') => {
+        current.push('"');
+        chars.next();
+      }
+      '"' => quoted = !quoted,
+      ',' if !quoted => fields.push(std::mem::take(&mut current)),
+      _ => current.push(ch),
+    }
+  }
+  fields.push(current);
+  fields.into_iter().map(|field| field.trim().to_string()).collect()
+}
+
+// rows keyed by lowercased header; leading blank lines (wmic) are skipped
+fn parse_csv_records(text: &str) -> Vec<HashMap<String, String>> {
+  let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+  let Some(header) = lines.next() else {
+    return Vec::new();
+  };
+  let columns: Vec<String> = split_csv_line(header)
+    .into_iter()
+    .map(|column| column.to_lowercase())
+    .collect();
+  lines
+    .map(|line| columns.iter().cloned().zip(split_csv_line(line)).collect())
+    .collect()
+}
+
+// "Key=Value" lines with a blank line between records; unlike /format:csv
+// this works on non en-US installs, where wmic can't find the csv stylesheet
+fn parse_wmic_list_records(text: &str) -> Vec<HashMap<String, String>> {
+  let mut records: Vec<HashMap<String, String>> = Vec::new();
+  let mut current: HashMap<String, String> = HashMap::new();
+  for line in text.lines() {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+      if !current.is_empty() {
+        records.push(std::mem::take(&mut current));
+      }
+      continue;
+    }
+    if let Some((key, value)) = trimmed.split_once('=') {
+      current.insert(key.trim().to_lowercase(), value.trim().to_string());
+    }
+  }
+  if !current.is_empty() {
+    records.push(current);
+  }
+  records
+}
+
+fn drives_from_records(records: &[HashMap<String, String>]) -> Vec<DriveInfo> {
+  let field = |record: &HashMap<String, String>, key: &str| {
+    record.get(key).map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
+  };
+  records
+    .iter()
+    .filter_map(|record| {
+      let size = field(record, "size").and_then(|value| parse_size_bytes(&value))?;
+      Some(DriveInfo {
+        model: field(record, "model").unwrap_or_else(|| "Disco".to_string()),
+        mediaType: field(record, "mediatype").filter(|value| !value.eq_ignore_ascii_case("unspecified")),
+        busType: field(record, "bustype"),
+        sizeGb: bytes_to_gb(size),
+        freeGb: field(record, "freespace")
+          .and_then(|value| parse_size_bytes(&value))
+          .map(bytes_to_gb),
+      })
+    })
+    .collect()
+}
+
+fn run_record_query(
+  program: &str,
+  args: &[&str],
+  parse: fn(&str) -> Vec<HashMap<String, String>>,
+) -> Vec<HashMap<String, String>> {
+  match std::process::Command::new(program).args(args).output() {
+    Ok(output) if output.status.success() => parse(&String::from_utf8_lossy(&output.stdout)),
+    Ok(_) => Vec::new(),
+    Err(error) => {
+      log_tool_error(program, &error);
+      Vec::new()
+    }
+  }
+}
+
+fn detect_drives() -> Vec<DriveInfo> {
+  let drives = drives_from_records(&run_record_query(
+    "powershell",
+    &["-NoProfile", "-Command", DRIVE_QUERY_SCRIPT],
+    parse_csv_records,
+  ));
+  if !drives.is_empty() {
+    return drives;
+  }
+  drives_from_records(&run_record_query(
+    "wmic",
+    &["diskdrive", "get", "Model,MediaType,Size", "/format:list"],
+    parse_wmic_list_records,
+  ))
+}
+
+// "Windows 11 Pro" + "22631" -> "Windows 11 Pro (22631)"
+fn format_os_name(long_version: Option<&str>, build: Option<&str>) -> String {
+  let name = long_version
+    .map(str::trim)
+    .filter(|value| !value.is_empty())
+    .unwrap_or("Windows");
+  match build.map(str::trim).filter(|value| !value.is_empty()) {
+    Some(build) if !name.contains(build) => format!("{} ({})", name, build),
+    _ => name.to_string(),
+  }
+}
+
+fn detect_os_name() -> String {
+  format_os_name(
+    System::long_os_version().as_deref(),
+    System::kernel_version().as_deref(),
+  )
+}
+
+#[derive(Default)]
+struct DisplayMode {
+  resolution: Option<String>,
+  refresh_hz: Option<u32>,
+}
+
+#[cfg_attr(not(windows), allow(dead_code))]
+fn format_resolution(width: u32, height: u32) -> Option<String> {
+  if width == 0 || height == 0 {
+    return None;
+  }
+  Some(format!("{}x{}", width, height))
+}
+
+#[cfg_attr(not(windows), allow(dead_code))]
+fn display_mode_from_settings(width: u32, height: u32, frequency: u32) -> DisplayMode {
+  DisplayMode {
+    resolution: format_resolution(width, height),
+    // DEVMODE uses 0 and 1 for "hardware default", not an actual rate
+    refresh_hz: if frequency > 1 { Some(frequency) } else { None },
+  }
+}
+
+// EnumDisplaySettings reports the primary display's physical mode, so it is
+// unaffected by DPI scaling; GetSystemMetrics is only the fallback
+#[cfg(windows)]
+fn detect_display_mode() -> DisplayMode {
+  use windows::core::PCWSTR;
+  use windows::Win32::Graphics::Gdi::{EnumDisplaySettingsW, DEVMODEW, ENUM_CURRENT_SETTINGS};
+  use windows::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_CXSCREEN, SM_CYSCREEN};
+  let mut mode = DEVMODEW {
+    dmSize: std::mem::size_of::<DEVMODEW>() as u16,
+    ..Default::default()
+  };
+  let found = unsafe { EnumDisplaySettingsW(PCWSTR::null(), ENUM_CURRENT_SETTINGS, &mut mode) };
+  let mut display = if found.as_bool() {
+    display_mode_from_settings(mode.dmPelsWidth, mode.dmPelsHeight, mode.dmDisplayFrequency)
+  } else {
+    DisplayMode::default()
+  };
+  if display.resolution.is_none() {
+    let (width, height) = unsafe { (GetSystemMetrics(SM_CXSCREEN), GetSystemMetrics(SM_CYSCREEN)) };
+    display.resolution = format_resolution(width.max(0) as u32, height.max(0) as u32);
+  }
+  display
+}
+
+#[cfg(not(windows))]
+fn detect_display_mode() -> DisplayMode {
+  DisplayMode::default()
+}
+
+// HDR counts as available when Windows HDR is already on for the primary
+// output (PQ color space) or the panel advertises at least DisplayHDR 400
+#[cfg(windows)]
+fn detect_hdr_capable() -> Option<bool> {
+  use windows::core::Interface;
+  use windows::Win32::Graphics::Dxgi::Common::DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020;
+  use windows::Win32::Graphics::Dxgi::{CreateDXGIFactory1, IDXGIFactory1, IDXGIOutput6};
+  unsafe {
+    let factory: IDXGIFactory1 = CreateDXGIFactory1().ok()?;
+    let adapter = factory.EnumAdapters1(0).ok()?;
+    let output = adapter.EnumOutputs(0).ok()?;
+    let output6: IDXGIOutput6 = output.cast().ok()?;
+    let desc = output6.GetDesc1().ok()?;
+    Some(desc.ColorSpace == DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020 || desc.MaxLuminance >= 400.0)
+  }
+}
+
+#[cfg(not(windows))]
+fn detect_hdr_capable() -> Option<bool> {
+  None
+}
+
 fn extract_ipv4s(text: &str) -> Vec<String> {
   let mut ips: Vec<String> = Vec::new();
   let mut buffer = String::new();
@@ -320,11 +900,23 @@ fn extract_ipv4s(text: &str) -> Vec<String> {
   ips
 }
 
+// 100.64.0.0/10 is the shared CGNAT range Tailscale hands out; the rest of
+// 100.* is ordinary public space
+fn is_cgnat_ipv4(ip: &str) -> bool {
+  let mut octets = ip.split('.');
+  octets.next() == Some("100")
+    && octets
+      .next()
+      .and_then(|value| value.parse::<u8>().ok())
+      .map(|second| (64..=127).contains(&second))
+      .unwrap_or(false)
+}
+
 fn score_ip(ip: &str) -> i32 {
   if ip.starts_with("127.") {
     return -1;
   }
-  if ip.starts_with("100.") {
+  if is_cgnat_ipv4(ip) {
     return 3;
   }
   if ip.starts_with("192.168.") {
@@ -343,12 +935,148 @@ fn score_ip(ip: &str) -> i32 {
   0
 }
 
+// ipconfig prints "fe80::1%12(Preferred)"; the zone and status suffix are
+// dropped before parsing
+fn extract_ipv6s(text: &str) -> Vec<String> {
+  text
+    .split_whitespace()
+    .filter(|token| token.contains(':'))
+    .filter_map(|token| {
+      let address = token.split(['%', '(']).next().unwrap_or(token);
+      address.parse::<std::net::Ipv6Addr>().ok()
+    })
+    .map(|address| address.to_string())
+    .collect()
+}
+
+fn score_ipv6(ip: &str) -> i32 {
+  let Ok(address) = ip.parse::<std::net::Ipv6Addr>() else {
+    return -1;
+  };
+  if address.is_loopback() || address.is_unspecified() {
+    return -1;
+  }
+  let segments = address.segments();
+  // tailscale ULA prefix fd7a:115c:a1e0::/48
+  if segments[0] == 0xfd7a && segments[1] == 0x115c && segments[2] == 0xa1e0 {
+    return 3;
+  }
+  if segments[0] & 0xfe00 == 0xfc00 {
+    return 2;
+  }
+  if segments[0] & 0xffc0 == 0xfe80 {
+    return 0;
+  }
+  if segments[0] & 0xe000 == 0x2000 {
+    return 2;
+  }
+  0
+}
+
+#[derive(Serialize, Clone)]
+struct LocalIp {
+  ip: String,
+  family: String,
+  score: i32,
+}
+
+fn local_ip_entry(address: std::net::IpAddr) -> LocalIp {
+  let ip = address.to_string();
+  match address {
+    std::net::IpAddr::V4(_) => LocalIp {
+      score: score_ip(&ip),
+      ip,
+      family: "ipv4".to_string(),
+    },
+    std::net::IpAddr::V6(_) => LocalIp {
+      score: score_ipv6(&ip),
+      ip,
+      family: "ipv6".to_string(),
+    },
+  }
+}
+
+// connecting a UDP socket sends nothing, but makes the OS pick the outbound
+// interface, whose address is then visible through local_addr
+fn probe_outbound_ip(bind: &str, target: &str) -> Option<std::net::IpAddr> {
+  let socket = std::net::UdpSocket::bind(bind).ok()?;
+  socket.connect(target).ok()?;
+  let address = socket.local_addr().ok()?.ip();
+  if address.is_unspecified() {
+    None
+  } else {
+    Some(address)
+  }
+}
+
+fn probe_outbound_ips() -> Vec<std::net::IpAddr> {
+  [("0.0.0.0:0", "8.8.8.8:80"), ("[::]:0", "[2001:4860:4860::8888]:80")]
+    .iter()
+    .filter_map(|(bind, target)| probe_outbound_ip(bind, target))
+    .collect()
+}
+
+// only "IPv4"/"IPv6" lines are scanned so masks and gateways are skipped
+fn ipconfig_addresses(text: &str) -> Vec<std::net::IpAddr> {
+  let mut addresses = Vec::new();
+  for line in text.lines() {
+    let lower = line.to_lowercase();
+    let Some((_, value)) = line.split_once(" : ").or_else(|| line.split_once(": ")) else {
+      continue;
+    };
+    if lower.contains("ipv4") {
+      addresses.extend(extract_ipv4s(value).iter().filter_map(|ip| ip.parse::<std::net::IpAddr>().ok()));
+    } else if lower.contains("ipv6") {
+      addresses.extend(extract_ipv6s(value).iter().filter_map(|ip| ip.parse::<std::net::IpAddr>().ok()));
+    }
+  }
+  addresses
+}
+
+fn rank_local_ips(addresses: Vec<std::net::IpAddr>) -> Vec<LocalIp> {
+  let mut seen: HashSet<std::net::IpAddr> = HashSet::new();
+  let mut ranked: Vec<LocalIp> = addresses
+    .into_iter()
+    .filter(|address| seen.insert(*address))
+    .map(local_ip_entry)
+    .filter(|entry| entry.score >= 0)
+    .collect();
+  // stable sort keeps ipconfig order among equal scores
+  ranked.sort_by(|a, b| b.score.cmp(&a.score));
+  ranked
+}
+
+#[tauri::command]
+fn detect_local_ips() -> Vec<LocalIp> {
+  let mut addresses: Vec<std::net::IpAddr> = Vec::new();
+  if cfg!(windows) {
+    match std::process::Command::new("ipconfig").output() {
+      Ok(output) => addresses.extend(ipconfig_addresses(&String::from_utf8_lossy(&output.stdout))),
+      Err(error) => log_tool_error("ipconfig", &error),
+    }
+  }
+  if addresses.is_empty() {
+    addresses = probe_outbound_ips();
+  }
+  rank_local_ips(addresses)
+}
+
+// the outbound socket address is language-agnostic; ipconfig is only scanned
+// when the probe fails, e.g. on an offline machine with no default route
 #[tauri::command]
 fn detect_local_ip() -> Option<String> {
+  if let Some(address) = probe_outbound_ip("0.0.0.0:0", "8.8.8.8:80") {
+    if score_ip(&address.to_string()) >= 0 {
+      return Some(address.to_string());
+    }
+  }
   if !cfg!(windows) {
     return None;
   }
-  let output = std::process::Command::new("ipconfig").output().ok()?;
+  let output = std::process::Command::new("ipconfig")
+    .output()
+    .map_err(|error| log_tool_error("ipconfig", &error))
+    .ok()?;
   let text = String::from_utf8_lossy(&output.stdout);
   let ips = extract_ipv4s(&text);
   if ips.is_empty() {
@@ -370,43 +1098,272 @@ fn detect_local_ip() -> Option<String> {
   }
 }
 
-#[tauri::command]
-fn get_local_pc_id() -> Result<String, String> {
-  if !cfg!(windows) {
-    return Err("Plataforma nao suportada.".to_string());
-  }
-  let mut parts: Vec<String> = Vec::new();
-  let uuid_lines = parse_wmic_lines(&["csproduct", "get", "UUID"]);
-  for line in uuid_lines {
-    if line.to_lowercase().contains("uuid") {
+#[derive(Serialize, Clone)]
+#[allow(non_snake_case)]
+struct IpCandidate {
+  ip: String,
+  score: i32,
+  adapterName: String,
+  likelyVirtual: bool,
+}
+
+const VIRTUAL_ADAPTER_KEYWORDS: [&str; 6] =
+  ["hyper-v", "vethernet", "virtualbox", "vmware", "tailscale", "wsl"];
+
+fn is_likely_virtual_adapter(name: &str) -> bool {
+  let lower = name.to_lowercase();
+  VIRTUAL_ADAPTER_KEYWORDS
+    .iter()
+    .any(|keyword| lower.contains(keyword))
+}
+
+// adapter headers are unindented lines ending in ':'; the indented lines
+// that follow belong to that adapter until the next header. blank lines are
+// ignored, so the spacing between blocks doesn't matter
+fn parse_ipconfig_blocks(text: &str) -> Vec<(String, Vec<String>)> {
+  let mut blocks: Vec<(String, Vec<String>)> = Vec::new();
+  for line in text.lines() {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
       continue;
     }
-    if !line.is_empty() {
-      parts.push(line);
-      break;
-    }
-  }
-  let bios_lines = parse_wmic_lines(&["bios", "get", "serialnumber"]);
-  for line in bios_lines {
-    if line.to_lowercase().contains("serial") {
+    let indented = line.starts_with(' ') || line.starts_with('\t');
+    if !indented && trimmed.ends_with(':') {
+      let name = trimmed.trim_end_matches(':').trim().to_string();
+      blocks.push((name, Vec::new()));
       continue;
     }
-    if !line.is_empty() {
-      parts.push(line);
-      break;
+    if let Some((_, lines)) = blocks.last_mut() {
+      lines.push(trimmed.to_string());
     }
   }
-  let mut system = System::new();
-  system.refresh_cpu();
-  if let Some(cpu) = system.cpus().first() {
-    parts.push(cpu.brand().to_string());
+  blocks
+}
+
+// only "IPv4" lines are kept so subnet masks and gateways don't show up as
+// candidates
+fn parse_ipconfig_adapters(text: &str) -> Vec<(String, Vec<String>)> {
+  parse_ipconfig_blocks(text)
+    .into_iter()
+    .map(|(name, lines)| {
+      let ips = lines
+        .iter()
+        .filter(|line| line.to_lowercase().contains("ipv4"))
+        .flat_map(|line| extract_ipv4s(line))
+        .collect();
+      (name, ips)
+    })
+    .collect()
+}
+
+#[tauri::command]
+fn list_ip_candidates_with_scores() -> Vec<IpCandidate> {
+  if !cfg!(windows) {
+    return Vec::new();
+  }
+  let output = match std::process::Command::new("ipconfig").output() {
+    Ok(output) => output,
+    Err(error) => {
+      log_tool_error("ipconfig", &error);
+      return Vec::new();
+    }
+  };
+  let text = String::from_utf8_lossy(&output.stdout);
+  let mut candidates: Vec<IpCandidate> = Vec::new();
+  for (adapter, ips) in parse_ipconfig_adapters(&text) {
+    let likely_virtual = is_likely_virtual_adapter(&adapter);
+    for ip in ips {
+      candidates.push(IpCandidate {
+        score: score_ip(&ip),
+        ip,
+        adapterName: adapter.clone(),
+        likelyVirtual: likely_virtual,
+      });
+    }
+  }
+  candidates
+}
+
+#[derive(Serialize, Clone)]
+#[allow(non_snake_case)]
+struct NetworkInterface {
+  name: String,
+  ipv4: Vec<String>,
+  ipv6: Vec<String>,
+  isUp: bool,
+  isLoopback: bool,
+  mac: Option<String>,
+}
+
+fn network_interface_from_block(name: String, lines: &[String]) -> NetworkInterface {
+  let mut ipv4: Vec<String> = Vec::new();
+  let mut ipv6: Vec<String> = Vec::new();
+  let mut mac: Option<String> = None;
+  let mut disconnected = false;
+  for line in lines {
+    let lower = line.to_lowercase();
+    let value = line.split_once(" : ").map(|(_, value)| value).unwrap_or(line);
+    if lower.contains("ipv4") {
+      ipv4.extend(extract_ipv4s(value));
+    } else if lower.contains("ipv6") {
+      ipv6.extend(extract_ipv6s(value));
+    }
+    if lower.contains("disconnected") || lower.contains("desconectad") {
+      disconnected = true;
+    }
+    if mac.is_none() {
+      mac = value
+        .split_whitespace()
+        .find(|token| is_mac_address(token))
+        .map(|token| token.to_uppercase());
+    }
+  }
+  ipv4.sort_by_key(|ip| std::cmp::Reverse(score_ip(ip)));
+  ipv6.sort_by_key(|ip| std::cmp::Reverse(score_ipv6(ip)));
+  let has_address = !ipv4.is_empty() || !ipv6.is_empty();
+  let is_loopback = name.to_lowercase().contains("loopback")
+    || (has_address
+      && ipv4.iter().all(|ip| ip.starts_with("127."))
+      && ipv6.iter().all(|ip| ip == "::1"));
+  NetworkInterface {
+    name,
+    ipv4,
+    ipv6,
+    isUp: !disconnected && has_address,
+    isLoopback: is_loopback,
+    mac,
+  }
+}
+
+#[tauri::command]
+fn list_network_interfaces() -> Result<Vec<NetworkInterface>, String> {
+  if !cfg!(windows) {
+    return Err("Plataforma nao suportada.".to_string());
+  }
+  let output = std::process::Command::new("ipconfig")
+    .arg("/all")
+    .output()
+    .map_err(|error| {
+      log_tool_error("ipconfig", &error);
+      error.to_string()
+    })?;
+  let text = String::from_utf8_lossy(&output.stdout);
+  Ok(
+    parse_ipconfig_blocks(&text)
+      .into_iter()
+      .map(|(name, lines)| network_interface_from_block(name, &lines))
+      .collect(),
+  )
+}
+
+// wmic, the CIM fallback and getmac can each take seconds; keep them off
+// the main thread
+#[tauri::command]
+async fn get_local_pc_id(include_cpu: Option<bool>, algorithm: Option<String>) -> Result<String, String> {
+  tauri::async_runtime::spawn_blocking(move || compute_local_pc_id(include_cpu, algorithm))
+    .await
+    .map_err(|error| error.to_string())?
+}
+
+fn compute_local_pc_id(include_cpu: Option<bool>, algorithm: Option<String>) -> Result<String, String> {
+  if !cfg!(windows) {
+    return Err("Plataforma nao suportada.".to_string());
   }
+  let uuid_lines = query_wmi_lines(
+    &["csproduct", "get", "UUID"],
+    "Win32_ComputerSystemProduct",
+    &["UUID"],
+  );
+  let uuid = first_wmi_value(&uuid_lines, "uuid");
+  let bios_lines = query_wmi_lines(&["bios", "get", "serialnumber"], "Win32_BIOS", &["SerialNumber"]);
+  let serial = first_wmi_value(&bios_lines, "serial");
+  let cpu = if include_cpu.unwrap_or(false) {
+    let mut system = System::new();
+    system.refresh_cpu();
+    system.cpus().first().map(|cpu| cpu.brand().to_string())
+  } else {
+    None
+  };
+  let mac = if uuid.is_none() && serial.is_none() {
+    detect_primary_mac()
+  } else {
+    None
+  };
 
-  let base = parts.join("|");
+  let base = local_pc_id_base(uuid, serial, mac, cpu);
   if base.trim().is_empty() {
     return Err("Nao foi possivel identificar este PC.".to_string());
   }
-  Ok(fnv1a_hash(&base))
+  hash_pc_id(&base, algorithm.as_deref())
+}
+
+// hashed input is "uuid|serial[|cpu]", or "mac[|cpu]" when the firmware
+// reports neither uuid nor serial; missing parts are skipped, not left empty
+fn local_pc_id_base(
+  uuid: Option<String>,
+  serial: Option<String>,
+  mac: Option<String>,
+  cpu: Option<String>,
+) -> String {
+  let mut parts: Vec<String> = Vec::new();
+  if uuid.is_some() || serial.is_some() {
+    parts.extend(uuid);
+    parts.extend(serial);
+  } else {
+    parts.extend(mac);
+  }
+  parts.extend(cpu);
+  parts.join("|")
+}
+
+// "AA-BB-CC-DD-EE-FF"; tunnel adapters print 8 groups and are rejected
+fn is_mac_address(value: &str) -> bool {
+  value.len() == 17
+    && value
+      .split('-')
+      .filter(|group| group.len() == 2 && group.chars().all(|ch| ch.is_ascii_hexdigit()))
+      .count()
+      == 6
+}
+
+// hyper-v, vmware, virtualbox, parallels and virtual pc; the hyper-v default
+// switch gets a new address on every boot
+const VIRTUAL_MAC_PREFIXES: [&str; 7] = [
+  "00-15-5D", "00-50-56", "00-0C-29", "00-05-69", "08-00-27", "00-1C-42", "00-03-FF",
+];
+
+// randomized wi-fi addresses and wi-fi direct adapters set the locally
+// administered bit, so they are never stable
+fn is_stable_physical_mac(mac: &str) -> bool {
+  let locally_administered = u8::from_str_radix(&mac[..2], 16)
+    .map(|octet| octet & 0x02 != 0)
+    .unwrap_or(true);
+  !locally_administered && !VIRTUAL_MAC_PREFIXES.iter().any(|prefix| mac.starts_with(prefix))
+}
+
+// `getmac /v /fo csv /nh` rows: connection name, adapter, address, transport.
+// disabled adapters print "N/A" and virtual ones are skipped; the lowest
+// remaining address wins so enumeration order does not change the id
+fn parse_getmac_csv(text: &str) -> Option<String> {
+  text
+    .lines()
+    .map(split_csv_line)
+    .filter(|fields| fields.len() >= 3)
+    .filter(|fields| !is_likely_virtual_adapter(&fields[0]) && !is_likely_virtual_adapter(&fields[1]))
+    .map(|fields| fields[2].to_uppercase())
+    .filter(|mac| is_mac_address(mac) && is_stable_physical_mac(mac))
+    .min()
+}
+
+fn detect_primary_mac() -> Option<String> {
+  match std::process::Command::new("getmac").args(["/v", "/fo", "csv", "/nh"]).output() {
+    Ok(output) if output.status.success() => parse_getmac_csv(&String::from_utf8_lossy(&output.stdout)),
+    Ok(_) => None,
+    Err(error) => {
+      log_tool_error("getmac", &error);
+      None
+    }
+  }
 }
 
 #[tauri::command]
@@ -419,17 +1376,52 @@ fn cancel_hardware_profile(request_id: String) -> bool {
 }
 
 #[tauri::command]
-fn get_hardware_profile(app: tauri::AppHandle, request_id: String) -> Result<HardwareProfile, String> {
+fn get_pending_hardware_cancels() -> Vec<String> {
+  if let Ok(guard) = cancel_set().lock() {
+    return guard.iter().cloned().collect();
+  }
+  Vec::new()
+}
+
+#[tauri::command]
+fn get_cached_hardware_profile() -> Option<HardwareProfile> {
+  hardware_profile_cache().latest()
+}
+
+#[tauri::command]
+fn get_hardware_profile(
+  app: tauri::AppHandle,
+  request_id: String,
+  force: Option<bool>,
+  ttl_secs: Option<u64>,
+) -> Result<(), String> {
   if request_id.trim().is_empty() {
     return Err("requestId invalido".to_string());
   }
   if !cfg!(windows) {
     return Err("Plataforma nao suportada.".to_string());
   }
+  let ttl = Duration::from_secs(ttl_secs.unwrap_or(HARDWARE_PROFILE_TTL_SECS));
+  let force = force.unwrap_or(false);
 
-  emit_progress(&app, &request_id, "Detectando CPU...");
-  if is_cancelled(&request_id) {
+  // detection shells out to wmic/powershell, so keep it off the IPC thread
+  std::thread::spawn(move || {
+    let probe = SystemHardwareProbe {
+      app: &app,
+      request_id: &request_id,
+    };
+    let result = hardware_profile_cache().get_or_detect(force, ttl, &probe);
+    // a cache hit skips the detection steps that normally clear it
     clear_cancel(&request_id);
+    emit_profile_ready(&app, &request_id, result);
+  });
+  Ok(())
+}
+
+fn detect_hardware_profile(app: &tauri::AppHandle, request_id: &str) -> Result<HardwareProfile, String> {
+  emit_progress(app, request_id, "Detectando CPU...");
+  if is_cancelled(request_id) {
+    clear_cancel(request_id);
     return Err("cancelled".to_string());
   }
   let mut system = System::new_all();
@@ -439,10 +1431,12 @@ fn get_hardware_profile(app: tauri::AppHandle, request_id: String) -> Result<Har
     .first()
     .map(|cpu| cpu.brand().to_string())
     .unwrap_or_else(|| "CPU desconhecida".to_string());
+  let cpu_physical_cores = system.physical_core_count().map(|count| count as u32);
+  let cpu_logical_threads = Some(system.cpus().len() as u32).filter(|count| *count > 0);
 
-  emit_progress(&app, &request_id, "Detectando RAM...");
-  if is_cancelled(&request_id) {
-    clear_cancel(&request_id);
+  emit_progress(app, request_id, "Detectando RAM...");
+  if is_cancelled(request_id) {
+    clear_cancel(request_id);
     return Err("cancelled".to_string());
   }
   system.refresh_memory();
@@ -456,35 +1450,60 @@ fn get_hardware_profile(app: tauri::AppHandle, request_id: String) -> Result<Har
   };
   let ram_gb = ram_gb.max(1);
 
-  emit_progress(&app, &request_id, "Detectando GPU...");
-  if is_cancelled(&request_id) {
-    clear_cancel(&request_id);
+  emit_progress(app, request_id, "Detectando GPU...");
+  if is_cancelled(request_id) {
+    clear_cancel(request_id);
     return Err("cancelled".to_string());
   }
-  let gpu_name = detect_gpu_name();
+  let gpus = detect_gpu_names();
+  let gpu_name = pick_best_gpu(&gpus).unwrap_or_else(|| "GPU desconhecida".to_string());
 
-  emit_progress(&app, &request_id, "Detectando armazenamento...");
-  if is_cancelled(&request_id) {
-    clear_cancel(&request_id);
+  emit_progress(app, request_id, "Detectando memoria de video...");
+  if is_cancelled(request_id) {
+    clear_cancel(request_id);
+    return Err("cancelled".to_string());
+  }
+  let vram_mb = detect_vram_mb();
+
+  emit_progress(app, request_id, "Detectando armazenamento...");
+  if is_cancelled(request_id) {
+    clear_cancel(request_id);
     return Err("cancelled".to_string());
   }
   let storage_summary = detect_storage_summary();
+  let drives = detect_drives();
+
+  emit_progress(app, request_id, "Detectando tela...");
+  if is_cancelled(request_id) {
+    clear_cancel(request_id);
+    return Err("cancelled".to_string());
+  }
+  let display = detect_display_mode();
+  let hdr_capable = detect_hdr_capable();
+  let os_name = detect_os_name();
 
-  emit_progress(&app, &request_id, "Finalizando...");
-  clear_cancel(&request_id);
+  emit_progress(app, request_id, "Finalizando...");
+  clear_cancel(request_id);
 
-  Ok(HardwareProfile {
+  let profile = HardwareProfile {
     cpuName: cpu_name,
+    cpuPhysicalCores: cpu_physical_cores,
+    cpuLogicalThreads: cpu_logical_threads,
     ramGb: ram_gb,
     gpuName: gpu_name,
+    gpus,
+    vramMb: vram_mb,
     storageSummary: storage_summary,
-    osName: Some("Windows".to_string()),
-    screenResolution: None,
-  })
+    drives,
+    osName: Some(os_name),
+    screenResolution: display.resolution,
+    refreshHz: display.refresh_hz,
+    hdrCapable: hdr_capable,
+  };
+  Ok(profile)
 }
 
-#[tauri::command]
-fn moonlight_list(path: String, host: String) -> Result<CommandOutput, String> {
+fn run_moonlight_list(path: &str, host: &str) -> Result<CommandOutput, String> {
   let trimmed = path.trim().trim_matches('"').trim_matches('\'');
   if trimmed.is_empty() {
     return Err("path vazio".to_string());
@@ -506,6 +1525,75 @@ fn moonlight_list(path: String, host: String) -> Result<CommandOutput, String> {
   })
 }
 
+#[tauri::command]
+fn moonlight_list(path: String, host: String) -> Result<CommandOutput, String> {
+  run_moonlight_list(&path, &host)
+}
+
+#[derive(Serialize, Clone)]
+struct MoonlightApp {
+  id: Option<u32>,
+  name: String,
+}
+
+const MOONLIGHT_APP_STATUS_MARKERS: [&str; 4] = ["(running)", "[running]", "(em execucao)", "*"];
+
+// accepts "Desktop" as well as "1. Desktop" / "2) Steam Big Picture (running)"
+fn parse_moonlight_app_line(line: &str) -> Option<MoonlightApp> {
+  let mut rest = line.trim();
+  let mut id: Option<u32> = None;
+  let digits: String = rest.chars().take_while(|ch| ch.is_ascii_digit()).collect();
+  if !digits.is_empty() {
+    let after = &rest[digits.len()..];
+    if let Some(name) = after.strip_prefix('.').or_else(|| after.strip_prefix(')')) {
+      id = digits.parse::<u32>().ok();
+      rest = name.trim();
+    }
+  }
+  let mut name = rest.to_string();
+  loop {
+    // ascii lowering keeps byte offsets aligned with `name`
+    let lower = name.to_ascii_lowercase();
+    let Some(marker) = MOONLIGHT_APP_STATUS_MARKERS
+      .iter()
+      .find(|marker| lower.ends_with(*marker))
+    else {
+      break;
+    };
+    name.truncate(name.len() - marker.len());
+    name = name.trim_end().to_string();
+  }
+  if name.is_empty() || name.ends_with(':') {
+    return None;
+  }
+  Some(MoonlightApp { id, name })
+}
+
+fn parse_moonlight_apps(stdout: &str) -> Vec<MoonlightApp> {
+  stdout.lines().filter_map(parse_moonlight_app_line).collect()
+}
+
+// `moonlight list` blocks until the host answers or times out
+#[tauri::command]
+async fn moonlight_list_apps(path: String, host: String) -> Result<Vec<MoonlightApp>, String> {
+  tauri::async_runtime::spawn_blocking(move || list_moonlight_apps(&path, &host))
+    .await
+    .map_err(|error| error.to_string())?
+}
+
+fn list_moonlight_apps(path: &str, host: &str) -> Result<Vec<MoonlightApp>, String> {
+  let output = run_moonlight_list(path, host)?;
+  if output.code != 0 {
+    let detail = output.stderr.trim();
+    return Err(if detail.is_empty() {
+      format!("Host inacessivel (codigo {}).", output.code)
+    } else {
+      format!("Host inacessivel (codigo {}): {}", output.code, detail)
+    });
+  }
+  Ok(parse_moonlight_apps(&output.stdout))
+}
+
 #[tauri::command]
 fn moonlight_pair(path: String, host: String) -> Result<CommandOutput, String> {
   let trimmed = path.trim().trim_matches('"').trim_matches('\'');
@@ -558,76 +1646,2195 @@ fn moonlight_stream(path: String, host: String, app: String) -> Result<CommandOu
   })
 }
 
-fn main() {
-  tauri::Builder::default()
-    .plugin(tauri_plugin_shell::init())
-    .plugin(tauri_plugin_dialog::init())
-    .setup(|app| {
-      let open = MenuItem::with_id(app, "open", "Abrir OpenDesk", true, Option::<&str>::None)?;
-      let status = MenuItem::with_id(app, "status", "Status: --", false, Option::<&str>::None)?;
-      let end_session =
-        MenuItem::with_id(app, "end_session", "Encerrar sessao", true, Option::<&str>::None)?;
-      let quit = MenuItem::with_id(app, "quit", "Sair", true, Option::<&str>::None)?;
-      let menu = Menu::with_items(
-        app,
-        &[
-          &open,
-          &status,
-          &end_session,
-          &PredefinedMenuItem::separator(app)?,
-          &quit,
-        ],
-      )?;
+#[cfg_attr(not(windows), allow(dead_code))]
+fn clamp_to_desktop(x: i32, y: i32, desktop: (i32, i32, i32, i32)) -> (i32, i32) {
+  let (left, top, width, height) = desktop;
+  if width <= 0 || height <= 0 {
+    return (x, y);
+  }
+  let right = left + width - 1;
+  let bottom = top + height - 1;
+  (x.clamp(left, right), y.clamp(top, bottom))
+}
 
-      let icon = app.default_window_icon().cloned();
-      let mut builder = TrayIconBuilder::with_id("main").menu(&menu);
-      if let Some(icon) = icon {
-        builder = builder.icon(icon);
-      }
-      builder
-        .on_menu_event(|app: &AppHandle, event: MenuEvent| match event.id().as_ref() {
-          "open" => {
-            if let Some(window) = app.get_webview_window("main") {
-              let _ = window.show();
-              let _ = window.set_focus();
-            }
-          }
-          "end_session" => {
-            let _ = app.emit("tray-action", "end_session");
-          }
-          "quit" => {
-            app.exit(0);
-          }
-          _ => {}
-        })
-        .on_tray_icon_event(|tray: &TrayIcon, event: TrayIconEvent| {
-          if let TrayIconEvent::DoubleClick { .. } = event {
-            if let Some(window) = tray.app_handle().get_webview_window("main") {
-              let _ = window.show();
-              let _ = window.set_focus();
-            }
-          }
-        })
-        .build(app)?;
-      Ok(())
-    })
-    .invoke_handler(tauri::generate_handler![
-      validate_exe_path,
-      is_process_running,
-      launch_exe,
-      launch_moonlight,
-      get_local_pc_id,
-      detect_local_ip,
-      get_hardware_profile,
-      cancel_hardware_profile,
-      detect_sunshine_path,
-      detect_moonlight_path,
-      start_sunshine,
-      start_moonlight,
-      moonlight_list,
-      moonlight_pair,
-      moonlight_stream
-    ])
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
+#[cfg(windows)]
+fn virtual_desktop_rect() -> (i32, i32, i32, i32) {
+  use windows::Win32::UI::WindowsAndMessaging::{
+    GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
+  };
+  unsafe {
+    (
+      GetSystemMetrics(SM_XVIRTUALSCREEN),
+      GetSystemMetrics(SM_YVIRTUALSCREEN),
+      GetSystemMetrics(SM_CXVIRTUALSCREEN),
+      GetSystemMetrics(SM_CYVIRTUALSCREEN),
+    )
+  }
+}
+
+#[cfg(windows)]
+fn read_cursor_position() -> Option<(i32, i32)> {
+  use windows::Win32::Foundation::POINT;
+  use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
+  let mut point = POINT::default();
+  unsafe { GetCursorPos(&mut point) }.ok()?;
+  Some((point.x, point.y))
+}
+
+#[cfg(not(windows))]
+fn read_cursor_position() -> Option<(i32, i32)> {
+  None
+}
+
+#[cfg(windows)]
+fn write_cursor_position(x: i32, y: i32) -> Result<(i32, i32), String> {
+  use windows::Win32::UI::WindowsAndMessaging::SetCursorPos;
+  let (x, y) = clamp_to_desktop(x, y, virtual_desktop_rect());
+  unsafe { SetCursorPos(x, y) }.map_err(|error| error.to_string())?;
+  Ok((x, y))
+}
+
+#[cfg(not(windows))]
+fn write_cursor_position(_x: i32, _y: i32) -> Result<(i32, i32), String> {
+  Err("Plataforma nao suportada.".to_string())
+}
+
+#[tauri::command]
+fn get_cursor_position() -> Option<(i32, i32)> {
+  read_cursor_position()
+}
+
+#[tauri::command]
+fn set_cursor_position(x: i32, y: i32) -> Result<(i32, i32), String> {
+  write_cursor_position(x, y)
+}
+
+#[derive(Serialize, Clone, Copy)]
+#[allow(non_snake_case)]
+struct MouseSettings {
+  speed: u32,
+  enhancePointerPrecision: bool,
+}
+
+#[derive(Clone, Copy)]
+struct RawMouseSettings {
+  speed: u32,
+  params: [i32; 3],
+}
+
+static MOUSE_SETTINGS_BACKUP: OnceLock<Mutex<Option<RawMouseSettings>>> = OnceLock::new();
+
+fn mouse_settings_backup() -> &'static Mutex<Option<RawMouseSettings>> {
+  MOUSE_SETTINGS_BACKUP.get_or_init(|| Mutex::new(None))
+}
+
+// Windows defaults for "Enhance pointer precision": threshold1, threshold2, acceleration
+const ENHANCED_POINTER_PARAMS: [i32; 3] = [6, 10, 1];
+
+fn encode_mouse_params(enhance_pointer_precision: bool) -> [i32; 3] {
+  if enhance_pointer_precision {
+    ENHANCED_POINTER_PARAMS
+  } else {
+    [0, 0, 0]
+  }
+}
+
+fn decode_mouse_settings(raw: RawMouseSettings) -> MouseSettings {
+  MouseSettings {
+    speed: raw.speed,
+    enhancePointerPrecision: raw.params[2] != 0,
+  }
+}
+
+// repeated set calls must not overwrite the settings the user had before
+// the first change; restore takes the slot and clears it
+fn remember_first_backup(slot: &mut Option<RawMouseSettings>, current: RawMouseSettings) {
+  if slot.is_none() {
+    *slot = Some(current);
+  }
+}
+
+#[cfg(windows)]
+fn read_raw_mouse_settings() -> Result<RawMouseSettings, String> {
+  use std::ffi::c_void;
+  use windows::Win32::UI::WindowsAndMessaging::{
+    SystemParametersInfoW, SPI_GETMOUSE, SPI_GETMOUSESPEED, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+  };
+  let mut params: [i32; 3] = [0, 0, 0];
+  let mut speed: i32 = 0;
+  unsafe {
+    SystemParametersInfoW(
+      SPI_GETMOUSE,
+      0,
+      Some(params.as_mut_ptr() as *mut c_void),
+      SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+    )
+    .map_err(|error| error.to_string())?;
+    SystemParametersInfoW(
+      SPI_GETMOUSESPEED,
+      0,
+      Some(&mut speed as *mut i32 as *mut c_void),
+      SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+    )
+    .map_err(|error| error.to_string())?;
+  }
+  Ok(RawMouseSettings {
+    speed: speed.clamp(1, 20) as u32,
+    params,
+  })
+}
+
+#[cfg(not(windows))]
+fn read_raw_mouse_settings() -> Result<RawMouseSettings, String> {
+  Err("Plataforma nao suportada.".to_string())
+}
+
+#[cfg(windows)]
+fn write_raw_mouse_settings(raw: RawMouseSettings) -> Result<(), String> {
+  use std::ffi::c_void;
+  use windows::Win32::UI::WindowsAndMessaging::{
+    SystemParametersInfoW, SPIF_SENDCHANGE, SPI_SETMOUSE, SPI_SETMOUSESPEED,
+  };
+  let mut params = raw.params;
+  unsafe {
+    SystemParametersInfoW(
+      SPI_SETMOUSE,
+      0,
+      Some(params.as_mut_ptr() as *mut c_void),
+      SPIF_SENDCHANGE,
+    )
+    .map_err(|error| error.to_string())?;
+    // SPI_SETMOUSESPEED takes the value itself in pvParam, not a pointer to it
+    SystemParametersInfoW(
+      SPI_SETMOUSESPEED,
+      0,
+      Some(raw.speed as usize as *mut c_void),
+      SPIF_SENDCHANGE,
+    )
+    .map_err(|error| error.to_string())?;
+  }
+  Ok(())
+}
+
+#[cfg(not(windows))]
+fn write_raw_mouse_settings(_raw: RawMouseSettings) -> Result<(), String> {
+  Err("Plataforma nao suportada.".to_string())
+}
+
+#[tauri::command]
+fn get_mouse_settings() -> Result<MouseSettings, String> {
+  read_raw_mouse_settings().map(decode_mouse_settings)
+}
+
+#[tauri::command]
+fn set_mouse_settings(
+  speed: Option<u32>,
+  enhance_pointer_precision: Option<bool>,
+) -> Result<MouseSettings, String> {
+  if let Some(value) = speed {
+    if !(1..=20).contains(&value) {
+      return Err("velocidade invalida (1-20)".to_string());
+    }
+  }
+  let current = read_raw_mouse_settings()?;
+  let next = RawMouseSettings {
+    speed: speed.unwrap_or(current.speed),
+    params: enhance_pointer_precision
+      .map(encode_mouse_params)
+      .unwrap_or(current.params),
+  };
+  if let Ok(mut guard) = mouse_settings_backup().lock() {
+    remember_first_backup(&mut guard, current);
+  }
+  write_raw_mouse_settings(next)?;
+  Ok(decode_mouse_settings(next))
+}
+
+#[tauri::command]
+fn restore_mouse_settings() -> Result<bool, String> {
+  let previous = match mouse_settings_backup().lock() {
+    Ok(mut guard) => guard.take(),
+    Err(_) => None,
+  };
+  let Some(previous) = previous else {
+    return Ok(false);
+  };
+  if let Err(error) = write_raw_mouse_settings(previous) {
+    if let Ok(mut guard) = mouse_settings_backup().lock() {
+      *guard = Some(previous);
+    }
+    return Err(error);
+  }
+  Ok(true)
+}
+
+const KNOWN_CONFLICTING_INPUT_SOFTWARE: [(&str, &str); 9] = [
+  ("parsecd.exe", "Parsec"),
+  ("anydesk.exe", "AnyDesk"),
+  ("teamviewer.exe", "TeamViewer"),
+  ("rustdesk.exe", "RustDesk"),
+  ("remoting_host.exe", "Chrome Remote Desktop"),
+  ("srserver.exe", "Splashtop"),
+  ("tvnserver.exe", "TightVNC"),
+  ("winvnc.exe", "UltraVNC"),
+  ("vncserver.exe", "RealVNC"),
+];
+
+fn list_process_names() -> Result<Vec<String>, String> {
+  let output = std::process::Command::new("tasklist")
+    .args(["/FO", "CSV", "/NH"])
+    .output()
+    .map_err(|error| {
+      log_tool_error("tasklist", &error);
+      error.to_string()
+    })?;
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  Ok(
+    stdout
+      .lines()
+      .filter_map(|line| line.split(',').next())
+      .map(|name| name.trim().trim_matches('"').to_string())
+      .filter(|name| !name.is_empty())
+      .collect(),
+  )
+}
+
+fn find_conflicting_software(processes: &[String]) -> Vec<String> {
+  let running: HashSet<String> = processes.iter().map(|name| name.to_lowercase()).collect();
+  KNOWN_CONFLICTING_INPUT_SOFTWARE
+    .iter()
+    .filter(|(exe, _)| running.contains(*exe))
+    .map(|(_, label)| label.to_string())
+    .collect()
+}
+
+#[tauri::command]
+fn detect_conflicting_input_software() -> Result<Vec<String>, String> {
+  if !cfg!(windows) {
+    return Ok(Vec::new());
+  }
+  let processes = list_process_names()?;
+  Ok(find_conflicting_software(&processes))
+}
+
+fn validate_picked_executable(picked: Option<PathBuf>) -> Result<Option<String>, String> {
+  let Some(path) = picked else {
+    return Ok(None);
+  };
+  let path = path.to_string_lossy().to_string();
+  if !validate_exe_path(path.clone()) {
+    return Err("executavel invalido".to_string());
+  }
+  Ok(Some(path))
+}
+
+// async so the blocking dialog doesn't run on the main thread
+#[tauri::command]
+async fn pick_executable(app: AppHandle) -> Result<Option<String>, String> {
+  let mut dialog = app.dialog().file().set_title("Selecionar executavel");
+  if cfg!(windows) {
+    dialog = dialog.add_filter("Executavel", &["exe"]);
+  }
+  let picked = match dialog.blocking_pick_file() {
+    Some(file) => Some(file.into_path().map_err(|error| error.to_string())?),
+    None => None,
+  };
+  validate_picked_executable(picked)
+}
+
+fn logical_to_physical(value: i32, scale: f64) -> i32 {
+  (value as f64 * scale).round() as i32
+}
+
+#[cfg(windows)]
+fn primary_monitor_scale() -> Option<f64> {
+  use windows::Win32::Foundation::POINT;
+  use windows::Win32::Graphics::Gdi::{MonitorFromPoint, MONITOR_DEFAULTTOPRIMARY};
+  use windows::Win32::UI::HiDpi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
+  let mut dpi_x: u32 = 0;
+  let mut dpi_y: u32 = 0;
+  unsafe {
+    let monitor = MonitorFromPoint(POINT { x: 0, y: 0 }, MONITOR_DEFAULTTOPRIMARY);
+    GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y).ok()?;
+  }
+  if dpi_x == 0 {
+    return None;
+  }
+  Some(dpi_x as f64 / 96.0)
+}
+
+#[cfg(not(windows))]
+fn primary_monitor_scale() -> Option<f64> {
+  None
+}
+
+#[tauri::command]
+fn detect_display_scaling() -> Option<f64> {
+  primary_monitor_scale()
+}
+
+#[tauri::command]
+fn logical_to_physical_point(x: i32, y: i32) -> (i32, i32) {
+  let scale = primary_monitor_scale().unwrap_or(1.0);
+  (logical_to_physical(x, scale), logical_to_physical(y, scale))
+}
+
+#[derive(Serialize, Clone)]
+struct PortStatus {
+  port: u16,
+  available: bool,
+  error: Option<String>,
+}
+
+fn check_port_available(port: u16, udp: bool) -> PortStatus {
+  let address = ("0.0.0.0", port);
+  let result = if udp {
+    std::net::UdpSocket::bind(address).map(|_| ())
+  } else {
+    std::net::TcpListener::bind(address).map(|_| ())
+  };
+  PortStatus {
+    port,
+    available: result.is_ok(),
+    error: result.err().map(|error| error.to_string()),
+  }
+}
+
+#[tauri::command]
+fn check_ports_available(ports: Vec<u16>, proto: String) -> Result<Vec<PortStatus>, String> {
+  let udp = match proto.trim().to_lowercase().as_str() {
+    "udp" => true,
+    "tcp" => false,
+    _ => return Err("protocolo invalido (tcp|udp)".to_string()),
+  };
+  Ok(
+    ports
+      .into_iter()
+      .map(|port| check_port_available(port, udp))
+      .collect(),
+  )
+}
+
+#[derive(Serialize, Clone)]
+#[allow(non_snake_case)]
+#[cfg_attr(not(windows), allow(dead_code))]
+struct ForegroundWindow {
+  title: String,
+  processName: Option<String>,
+  pid: u32,
+}
+
+#[cfg_attr(not(windows), allow(dead_code))]
+fn shape_foreground_window(title: &[u16], pid: u32, process_name: Option<String>) -> ForegroundWindow {
+  ForegroundWindow {
+    title: String::from_utf16_lossy(title).trim().to_string(),
+    processName: process_name,
+    pid,
+  }
+}
+
+#[cfg(windows)]
+fn process_name_for_pid(pid: u32) -> Option<String> {
+  let pid = sysinfo::Pid::from_u32(pid);
+  let mut system = System::new();
+  if !system.refresh_process(pid) {
+    return None;
+  }
+  system.process(pid).map(|process| process.name().to_string())
+}
+
+#[cfg(windows)]
+fn read_foreground_window() -> Option<ForegroundWindow> {
+  use windows::Win32::UI::WindowsAndMessaging::{
+    GetForegroundWindow, GetWindowTextLengthW, GetWindowTextW, GetWindowThreadProcessId,
+  };
+  unsafe {
+    let hwnd = GetForegroundWindow();
+    if hwnd.0.is_null() {
+      return None;
+    }
+    let length = GetWindowTextLengthW(hwnd).max(0) as usize;
+    let mut buffer: Vec<u16> = vec![0; length + 1];
+    let copied = GetWindowTextW(hwnd, &mut buffer).max(0) as usize;
+    let mut pid: u32 = 0;
+    GetWindowThreadProcessId(hwnd, Some(&mut pid));
+    Some(shape_foreground_window(&buffer[..copied], pid, process_name_for_pid(pid)))
+  }
+}
+
+#[cfg(not(windows))]
+fn read_foreground_window() -> Option<ForegroundWindow> {
+  None
+}
+
+#[tauri::command]
+fn get_foreground_window() -> Option<ForegroundWindow> {
+  read_foreground_window()
+}
+
+fn network_cost_is_metered(cost_type: &str) -> Option<bool> {
+  match cost_type.trim().to_lowercase().as_str() {
+    "unrestricted" => Some(false),
+    "fixed" | "variable" => Some(true),
+    _ => None,
+  }
+}
+
+// powershell startup plus the WinRT load takes a while; query on a
+// blocking worker
+#[tauri::command]
+async fn is_network_metered() -> Result<Option<bool>, String> {
+  tauri::async_runtime::spawn_blocking(query_network_metered)
+    .await
+    .map_err(|error| error.to_string())
+}
+
+fn query_network_metered() -> Option<bool> {
+  if !cfg!(windows) {
+    return None;
+  }
+  let script = "[void][Windows.Networking.Connectivity.NetworkInformation,Windows.Networking.Connectivity,ContentType=WindowsRuntime]; \
+    $connection = [Windows.Networking.Connectivity.NetworkInformation]::GetInternetConnectionProfile(); \
+    if ($connection) { $connection.GetConnectionCost().NetworkCostType }";
+  let output = std::process::Command::new("powershell")
+    .args(["-NoProfile", "-Command", script])
+    .output()
+    .map_err(|error| log_tool_error("powershell", &error))
+    .ok()?;
+  if !output.status.success() {
+    return None;
+  }
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  network_cost_is_metered(stdout.lines().next().unwrap_or(""))
+}
+
+#[derive(Serialize, Clone)]
+#[allow(non_snake_case)]
+struct GpuTelemetry {
+  tempC: Option<u32>,
+  coreClockMhz: Option<u32>,
+  memClockMhz: Option<u32>,
+  utilizationPct: Option<u32>,
+}
+
+// readings are independent; a gpu that answers none of them counts as
+// unavailable
+fn shape_gpu_telemetry(
+  temp_c: Option<u32>,
+  core_clock_mhz: Option<u32>,
+  mem_clock_mhz: Option<u32>,
+  utilization_pct: Option<u32>,
+) -> Option<GpuTelemetry> {
+  if temp_c.is_none() && core_clock_mhz.is_none() && mem_clock_mhz.is_none() && utilization_pct.is_none() {
+    return None;
+  }
+  Some(GpuTelemetry {
+    tempC: temp_c,
+    coreClockMhz: core_clock_mhz,
+    memClockMhz: mem_clock_mhz,
+    utilizationPct: utilization_pct,
+  })
+}
+
+// nvml is loaded from the nvidia driver (nvml.dll) at runtime, so machines
+// without it just get None; initialized once because overlays poll this
+static NVML: OnceLock<Option<nvml_wrapper::Nvml>> = OnceLock::new();
+
+fn nvml() -> Option<&'static nvml_wrapper::Nvml> {
+  NVML.get_or_init(|| nvml_wrapper::Nvml::init().ok()).as_ref()
+}
+
+// nvidia only for now; other vendors return None
+#[tauri::command]
+fn sample_gpu_telemetry() -> Option<GpuTelemetry> {
+  use nvml_wrapper::enum_wrappers::device::{Clock, TemperatureSensor};
+  let device = nvml()?.device_by_index(0).ok()?;
+  shape_gpu_telemetry(
+    device.temperature(TemperatureSensor::Gpu).ok(),
+    device.clock_info(Clock::Graphics).ok(),
+    device.clock_info(Clock::Memory).ok(),
+    device.utilization_rates().ok().map(|rates| rates.gpu),
+  )
+}
+
+#[derive(Serialize, Clone)]
+#[allow(non_snake_case)]
+struct DecoderSupport {
+  gpuName: String,
+  h264: bool,
+  hevc: bool,
+  av1: bool,
+  confidence: String,
+}
+
+// (series token, first model, last model, hevc, av1). ranges cover consumer
+// model numbers only, so workstation names like "Quadro RTX 3000" or
+// "RTX 2000 Ada" are classified by the architecture checks instead
+const DECODER_MODEL_RANGES: [(&str, u32, u32, bool, bool); 16] = [
+  ("gt", 1030, 1030, true, false),
+  ("gtx", 1050, 1099, true, false),
+  ("gtx", 1630, 1699, true, false),
+  ("rtx", 2050, 2099, true, false),
+  ("rtx", 3050, 3099, true, true),
+  ("rtx", 4050, 4099, true, true),
+  ("rtx", 5050, 5099, true, true),
+  ("rx", 460, 599, true, false),
+  ("rx", 5300, 5799, true, false),
+  // navi 24 (rx 6400/6500) ships without an av1 decoder
+  ("rx", 6300, 6599, true, false),
+  ("rx", 6600, 6999, true, true),
+  ("rx", 7400, 7999, true, true),
+  ("rx", 9000, 9999, true, true),
+  ("uhd", 600, 699, true, false),
+  ("uhd", 700, 799, true, true),
+  ("hd", 500, 699, true, false),
+];
+
+fn gpu_name_tokens(gpu_name: &str) -> Vec<String> {
+  gpu_name
+    .to_lowercase()
+    .split(|ch: char| !ch.is_ascii_alphanumeric())
+    .filter(|token| !token.is_empty())
+    .map(str::to_string)
+    .collect()
+}
+
+// the model number follows the series token, sometimes after a word
+// ("UHD Graphics 770")
+fn model_after(tokens: &[String], series: &str) -> Option<u32> {
+  let index = tokens.iter().position(|token| token == series)?;
+  tokens
+    .iter()
+    .skip(index + 1)
+    .take(2)
+    .find_map(|token| token.parse::<u32>().ok())
+}
+
+// (h264, hevc, av1, confidence)
+fn decoder_hint(gpu_name: &str) -> (bool, bool, bool, &'static str) {
+  let tokens = gpu_name_tokens(gpu_name);
+  let has = |word: &str| tokens.iter().any(|token| token == word);
+  if has("ada") || has("blackwell") {
+    return (true, true, true, "high");
+  }
+  // "RTX A2000" / "RTX A6000" are ampere workstation cards
+  let ampere_pro = tokens
+    .iter()
+    .any(|token| token.len() == 5 && token.starts_with('a') && token[1..].parse::<u32>().is_ok());
+  if has("rtx") && ampere_pro {
+    return (true, true, true, "high");
+  }
+  // the quadro brand ended with turing, none of which decode av1
+  if has("quadro") {
+    return (true, true, false, if has("rtx") { "high" } else { "medium" });
+  }
+  for (series, first, last, hevc, av1) in DECODER_MODEL_RANGES.iter() {
+    if let Some(model) = model_after(&tokens, series) {
+      if (*first..=*last).contains(&model) {
+        return (true, *hevc, *av1, "high");
+      }
+    }
+  }
+  if has("arc") || (has("iris") && has("xe")) {
+    return (true, true, true, "high");
+  }
+  if has("nvidia") || has("geforce") || has("radeon") || has("amd") || has("intel") {
+    return (true, true, false, "low");
+  }
+  (false, false, false, "unknown")
+}
+
+fn infer_decoder_support(gpu_name: &str) -> DecoderSupport {
+  let (h264, hevc, av1, confidence) = decoder_hint(gpu_name);
+  DecoderSupport {
+    gpuName: gpu_name.to_string(),
+    h264,
+    hevc,
+    av1,
+    confidence: confidence.to_string(),
+  }
+}
+
+// detect_gpu_name may fall back to powershell; query on a blocking worker
+#[tauri::command]
+async fn detect_decoder_support() -> Result<DecoderSupport, String> {
+  tauri::async_runtime::spawn_blocking(|| infer_decoder_support(&detect_gpu_name()))
+    .await
+    .map_err(|error| error.to_string())
+}
+
+#[derive(Serialize, Clone)]
+struct ToolStatus {
+  name: String,
+  status: String,
+  detail: Option<String>,
+}
+
+const SYSTEM_TOOL_PROBES: [(&str, &[&str]); 5] = [
+  ("tasklist", &["/NH"]),
+  ("wmic", &["os", "get", "caption"]),
+  ("where", &["cmd"]),
+  ("ipconfig", &[]),
+  ("powershell", &["-NoProfile", "-Command", "exit 0"]),
+];
+
+// "missing" means the tool could not be spawned at all; "error" means it ran
+// but exited with failure (empty results from those are not trustworthy)
+fn classify_tool_probe(result: &std::io::Result<std::process::Output>) -> (&'static str, Option<String>) {
+  match result {
+    Ok(output) if output.status.success() => ("ok", None),
+    Ok(output) => (
+      "error",
+      Some(format!("exit code {}", output.status.code().unwrap_or(-1))),
+    ),
+    Err(error) if error.kind() == std::io::ErrorKind::NotFound => ("missing", None),
+    Err(error) => ("error", Some(error.to_string())),
+  }
+}
+
+fn probe_system_tools() -> Vec<ToolStatus> {
+  if !cfg!(windows) {
+    return Vec::new();
+  }
+  SYSTEM_TOOL_PROBES
+    .iter()
+    .map(|(tool, args)| {
+      let result = std::process::Command::new(tool).args(*args).output();
+      let (status, detail) = classify_tool_probe(&result);
+      ToolStatus {
+        name: tool.to_string(),
+        status: status.to_string(),
+        detail,
+      }
+    })
+    .collect()
+}
+
+// a cold wmic plus powershell takes seconds; probe on a blocking worker
+#[tauri::command]
+async fn check_system_tools() -> Result<Vec<ToolStatus>, String> {
+  tauri::async_runtime::spawn_blocking(probe_system_tools)
+    .await
+    .map_err(|error| error.to_string())
+}
+
+#[derive(Serialize, Clone)]
+struct EventDescriptor {
+  name: String,
+  description: String,
+  payload: String,
+}
+
+#[tauri::command]
+fn list_backend_events() -> Vec<EventDescriptor> {
+  BACKEND_EVENTS
+    .iter()
+    .map(|(name, description, payload)| EventDescriptor {
+      name: name.to_string(),
+      description: description.to_string(),
+      payload: payload.to_string(),
+    })
+    .collect()
+}
+
+#[derive(Serialize, Clone)]
+struct KnownHost {
+  name: String,
+  address: Option<String>,
+  paired: bool,
+}
+
+const MOONLIGHT_REGISTRY_HOSTS: &str =
+  r"HKCU\Software\Moonlight Game Streaming Project\Moonlight\hosts";
+
+type MoonlightHostEntries = BTreeMap<u32, HashMap<String, String>>;
+
+// QSettings INI layout: "[hosts]" section with "<index>\<key>=<value>" lines
+fn parse_moonlight_ini(text: &str) -> MoonlightHostEntries {
+  let mut entries: MoonlightHostEntries = BTreeMap::new();
+  let mut in_hosts = false;
+  for line in text.lines() {
+    let trimmed = line.trim();
+    if trimmed.starts_with('[') && trimmed.ends_with(']') {
+      in_hosts = trimmed.eq_ignore_ascii_case("[hosts]");
+      continue;
+    }
+    if !in_hosts {
+      continue;
+    }
+    let Some((key, value)) = trimmed.split_once('=') else {
+      continue;
+    };
+    let Some((index, field)) = key.split_once('\\') else {
+      continue;
+    };
+    if let Ok(index) = index.trim().parse::<u32>() {
+      entries
+        .entry(index)
+        .or_default()
+        .insert(field.trim().to_lowercase(), value.trim().to_string());
+    }
+  }
+  entries
+}
+
+// `reg query <key> /s` layout: one "HKEY_...\hosts\<index>" line per host,
+// followed by indented "name    TYPE    data" value lines
+fn parse_moonlight_registry(text: &str) -> MoonlightHostEntries {
+  let mut entries: MoonlightHostEntries = BTreeMap::new();
+  let mut current: Option<u32> = None;
+  for line in text.lines() {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+      continue;
+    }
+    if trimmed.starts_with("HKEY_") {
+      current = trimmed
+        .rsplit('\\')
+        .next()
+        .and_then(|segment| segment.parse::<u32>().ok());
+      continue;
+    }
+    let Some(index) = current else {
+      continue;
+    };
+    let mut parts = trimmed.splitn(3, "    ");
+    let field = parts.next().unwrap_or("").trim();
+    let _kind = parts.next();
+    let value = parts.next().unwrap_or("").trim();
+    if !field.is_empty() {
+      entries
+        .entry(index)
+        .or_default()
+        .insert(field.to_lowercase(), value.to_string());
+    }
+  }
+  entries
+}
+
+fn known_hosts_from_entries(entries: MoonlightHostEntries) -> Vec<KnownHost> {
+  let non_empty = |fields: &HashMap<String, String>, key: &str| {
+    fields
+      .get(key)
+      .map(|value| value.trim().to_string())
+      .filter(|value| !value.is_empty() && value != "@ByteArray()")
+  };
+  entries
+    .values()
+    .filter_map(|fields| {
+      let address = ["manualaddress", "localaddress", "remoteaddress"]
+        .iter()
+        .find_map(|key| non_empty(fields, *key));
+      let name = non_empty(fields, "hostname").or_else(|| address.clone())?;
+      Some(KnownHost {
+        name,
+        address,
+        paired: non_empty(fields, "srvcert").is_some(),
+      })
+    })
+    .collect()
+}
+
+#[tauri::command]
+fn moonlight_known_hosts(config_dir: Option<String>) -> Result<Vec<KnownHost>, String> {
+  if let Some(dir) = config_dir {
+    let trimmed = dir.trim().trim_matches('"').trim_matches('\'');
+    if trimmed.is_empty() {
+      return Err("pasta vazia".to_string());
+    }
+    let ini_path = Path::new(trimmed).join("Moonlight.ini");
+    let text = std::fs::read_to_string(&ini_path)
+      .map_err(|_| format!("Moonlight.ini nao encontrado em {}", trimmed))?;
+    return Ok(known_hosts_from_entries(parse_moonlight_ini(&text)));
+  }
+  if !cfg!(windows) {
+    return Err("Plataforma nao suportada.".to_string());
+  }
+  let output = std::process::Command::new("reg")
+    .args(["query", MOONLIGHT_REGISTRY_HOSTS, "/s"])
+    .output()
+    .map_err(|error| {
+      log_tool_error("reg", &error);
+      error.to_string()
+    })?;
+  if !output.status.success() {
+    return Err("Nenhuma configuracao do Moonlight encontrada.".to_string());
+  }
+  let text = String::from_utf8_lossy(&output.stdout);
+  Ok(known_hosts_from_entries(parse_moonlight_registry(&text)))
+}
+
+#[cfg(windows)]
+fn process_is_elevated() -> bool {
+  use std::ffi::c_void;
+  use windows::Win32::Foundation::{CloseHandle, HANDLE};
+  use windows::Win32::Security::{GetTokenInformation, TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY};
+  use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+  unsafe {
+    let mut token = HANDLE::default();
+    if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token).is_err() {
+      return false;
+    }
+    let mut elevation = TOKEN_ELEVATION::default();
+    let mut returned: u32 = 0;
+    let result = GetTokenInformation(
+      token,
+      TokenElevation,
+      Some(&mut elevation as *mut TOKEN_ELEVATION as *mut c_void),
+      std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+      &mut returned,
+    );
+    let _ = CloseHandle(token);
+    result.is_ok() && elevation.TokenIsElevated != 0
+  }
+}
+
+#[cfg(not(windows))]
+fn process_is_elevated() -> bool {
+  false
+}
+
+// quotes one argument so CommandLineToArgvW in the relaunched process splits
+// it back out unchanged: backslashes are only special before a quote
+fn quote_windows_arg(arg: &str) -> String {
+  if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+    return arg.to_string();
+  }
+  let mut quoted = String::from("\"");
+  let mut backslashes = 0;
+  for ch in arg.chars() {
+    match ch {
+      '\\' => backslashes += 1,
+      '"' => {
+        quoted.push_str(&"\\".repeat(backslashes * 2 + 1));
+        quoted.push('"');
+        backslashes = 0;
+      }
+      _ => {
+        quoted.push_str(&"\\".repeat(backslashes));
+        quoted.push(ch);
+        backslashes = 0;
+      }
+    }
+  }
+  quoted.push_str(&"\\".repeat(backslashes * 2));
+  quoted.push('"');
+  quoted
+}
+
+fn build_relaunch_parameters(args: &[String]) -> String {
+  args
+    .iter()
+    .map(|arg| quote_windows_arg(arg))
+    .collect::<Vec<String>>()
+    .join(" ")
+}
+
+#[cfg(windows)]
+fn shell_execute_runas(exe: &str, parameters: &str) -> Result<(), String> {
+  use windows::core::{w, HSTRING, PCWSTR};
+  use windows::Win32::Foundation::{GetLastError, ERROR_CANCELLED};
+  use windows::Win32::UI::Shell::ShellExecuteW;
+  use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+  let file = HSTRING::from(exe);
+  let parameters = HSTRING::from(parameters);
+  unsafe {
+    let result = ShellExecuteW(None, w!("runas"), &file, &parameters, PCWSTR::null(), SW_SHOWNORMAL);
+    // values above 32 mean the process was started
+    if result.0 as isize > 32 {
+      return Ok(());
+    }
+    if GetLastError() == ERROR_CANCELLED {
+      return Err("Elevacao recusada pelo usuario.".to_string());
+    }
+    Err(format!("Falha ao reiniciar como administrador (codigo {}).", result.0 as isize))
+  }
+}
+
+#[cfg(not(windows))]
+fn shell_execute_runas(_exe: &str, _parameters: &str) -> Result<(), String> {
+  Err("Plataforma nao suportada.".to_string())
+}
+
+#[tauri::command]
+fn is_elevated() -> bool {
+  process_is_elevated()
+}
+
+#[tauri::command]
+fn relaunch_as_admin(app: AppHandle) -> Result<(), String> {
+  if !cfg!(windows) {
+    return Err("Plataforma nao suportada.".to_string());
+  }
+  let exe = std::env::current_exe().map_err(|error| error.to_string())?;
+  let args: Vec<String> = std::env::args().skip(1).collect();
+  // a declined UAC prompt returns early and this instance keeps running
+  shell_execute_runas(&exe.to_string_lossy(), &build_relaunch_parameters(&args))?;
+  app.exit(0);
+  Ok(())
+}
+
+#[derive(Serialize, Clone)]
+struct DiagnosticItem {
+  id: String,
+  status: String,
+  detail: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+struct DiagnosticsReport {
+  overall: String,
+  items: Vec<DiagnosticItem>,
+}
+
+// keep in sync with SUNSHINE_TCP_PORTS / SUNSHINE_UDP_PORT_RANGES in src/lib/streamingGate.ts
+const SUNSHINE_TCP_PORTS: [u16; 3] = [47984, 47989, 47990];
+const SUNSHINE_UDP_PORT_RANGE: (u16, u16) = (47998, 48010);
+
+// (udp, port) for every port a stream needs
+fn sunshine_stream_ports() -> Vec<(bool, u16)> {
+  SUNSHINE_TCP_PORTS
+    .iter()
+    .map(|port| (false, *port))
+    .chain((SUNSHINE_UDP_PORT_RANGE.0..=SUNSHINE_UDP_PORT_RANGE.1).map(|port| (true, port)))
+    .collect()
+}
+
+fn format_stream_port(udp: bool, port: u16) -> String {
+  format!("{}/{}", if udp { "udp" } else { "tcp" }, port)
+}
+
+fn diagnostic_item(id: &str, status: &str, detail: Option<String>) -> DiagnosticItem {
+  DiagnosticItem {
+    id: id.to_string(),
+    status: status.to_string(),
+    detail,
+  }
+}
+
+fn diagnose_system_tools(tools: &[ToolStatus]) -> DiagnosticItem {
+  let broken: Vec<String> = tools
+    .iter()
+    .filter(|tool| tool.status != "ok")
+    .map(|tool| format!("{} ({})", tool.name, tool.status))
+    .collect();
+  // wmic is deprecated on recent Windows builds, so its absence alone is a warning
+  let critical = tools
+    .iter()
+    .any(|tool| tool.status == "missing" && tool.name != "wmic");
+  let status = if critical {
+    "fail"
+  } else if !broken.is_empty() {
+    "warn"
+  } else {
+    "pass"
+  };
+  diagnostic_item("system_tools", status, (!broken.is_empty()).then(|| broken.join(", ")))
+}
+
+fn diagnose_local_ip(candidates: &[IpCandidate]) -> DiagnosticItem {
+  let best = candidates
+    .iter()
+    .filter(|candidate| candidate.score >= 0)
+    .max_by_key(|candidate| (!candidate.likelyVirtual, candidate.score));
+  match best {
+    None => diagnostic_item("local_ip", "fail", Some("nenhum IP local encontrado".to_string())),
+    Some(candidate) if candidate.likelyVirtual => diagnostic_item(
+      "local_ip",
+      "warn",
+      Some(format!("{} ({}) parece virtual", candidate.ip, candidate.adapterName)),
+    ),
+    Some(candidate) => diagnostic_item(
+      "local_ip",
+      "pass",
+      Some(format!("{} ({})", candidate.ip, candidate.adapterName)),
+    ),
+  }
+}
+
+// a port held by Sunshine itself is the expected state on a host waiting for
+// a stream; only ports taken by another process are a problem
+fn diagnose_ports(statuses: &[(bool, PortStatus)], sunshine_owned: &HashSet<(bool, u16)>) -> DiagnosticItem {
+  let busy: Vec<String> = statuses
+    .iter()
+    .filter(|(udp, status)| !status.available && !sunshine_owned.contains(&(*udp, status.port)))
+    .map(|(udp, status)| format_stream_port(*udp, status.port))
+    .collect();
+  if busy.is_empty() {
+    diagnostic_item("ports", "pass", None)
+  } else {
+    diagnostic_item("ports", "warn", Some(format!("em uso: {}", busy.join(", "))))
+  }
+}
+
+// "  TCP    0.0.0.0:47989    0.0.0.0:0    LISTENING    4242"
+// "  UDP    [::]:47998       *:*                       4242"
+fn parse_netstat_port_owners(text: &str) -> Vec<(bool, u16, u32)> {
+  text
+    .lines()
+    .filter_map(|line| {
+      let fields: Vec<&str> = line.split_whitespace().collect();
+      let udp = match fields.first()?.to_ascii_uppercase().as_str() {
+        "TCP" => false,
+        "UDP" => true,
+        _ => return None,
+      };
+      let port = fields.get(1)?.rsplit(':').next()?.parse::<u16>().ok()?;
+      let pid = fields.last()?.parse::<u32>().ok()?;
+      Some((udp, port, pid))
+    })
+    .collect()
+}
+
+fn sunshine_owned_ports() -> HashSet<(bool, u16)> {
+  let output = match std::process::Command::new("netstat").arg("-ano").output() {
+    Ok(output) => output,
+    Err(error) => {
+      log_tool_error("netstat", &error);
+      return HashSet::new();
+    }
+  };
+  let owners = parse_netstat_port_owners(&String::from_utf8_lossy(&output.stdout));
+  let mut system = System::new();
+  system.refresh_processes();
+  owners
+    .into_iter()
+    .filter(|(_, _, pid)| {
+      system
+        .process(sysinfo::Pid::from_u32(*pid))
+        .map(|process| process.name().to_lowercase().starts_with("sunshine"))
+        .unwrap_or(false)
+    })
+    .map(|(udp, port, _)| (udp, port))
+    .collect()
+}
+
+#[derive(Clone)]
+struct FirewallRule {
+  name: String,
+  enabled: bool,
+  inbound: bool,
+  allow: bool,
+  protocol: String,
+  local_ports: String,
+}
+
+// netsh labels are localized and its accents arrive mangled, so keys are
+// reduced to ascii letters before matching ("Ação" -> "ao")
+fn firewall_key(label: &str) -> String {
+  label
+    .chars()
+    .filter(|ch| ch.is_ascii_alphabetic())
+    .collect::<String>()
+    .to_ascii_lowercase()
+}
+
+fn parse_netsh_rules(text: &str) -> Vec<FirewallRule> {
+  let mut rules: Vec<FirewallRule> = Vec::new();
+  for line in text.lines() {
+    let Some((label, value)) = line.split_once(':') else {
+      continue;
+    };
+    let key = firewall_key(label);
+    let value = value.trim();
+    let lower = value.to_lowercase();
+    if key == "rulename" || key == "nomedaregra" {
+      rules.push(FirewallRule {
+        name: value.to_string(),
+        enabled: false,
+        inbound: false,
+        allow: false,
+        protocol: "any".to_string(),
+        // rules without a LocalPort line apply to every port
+        local_ports: "any".to_string(),
+      });
+      continue;
+    }
+    let Some(rule) = rules.last_mut() else {
+      continue;
+    };
+    match key.as_str() {
+      "enabled" | "habilitado" => rule.enabled = lower == "yes" || lower == "sim",
+      "direction" | "direo" | "direcao" => rule.inbound = lower == "in" || lower == "entrada",
+      "action" | "ao" | "acao" => rule.allow = lower == "allow" || lower == "permitir",
+      "protocol" | "protocolo" => rule.protocol = lower,
+      "localport" | "portalocal" => rule.local_ports = lower,
+      _ => {}
+    }
+  }
+  rules
+}
+
+// "47984,47989", "47998-48010" or "any"
+fn port_spec_covers(spec: &str, port: u16) -> bool {
+  spec.split(',').map(str::trim).any(|part| {
+    if part == "any" || part == "qualquer" {
+      return true;
+    }
+    match part.split_once('-') {
+      Some((start, end)) => match (start.trim().parse::<u16>(), end.trim().parse::<u16>()) {
+        (Ok(start), Ok(end)) => (start..=end).contains(&port),
+        _ => false,
+      },
+      None => part.parse::<u16>() == Ok(port),
+    }
+  })
+}
+
+fn rule_applies(rule: &FirewallRule, udp: bool, port: u16) -> bool {
+  let protocol_matches = match rule.protocol.as_str() {
+    "tcp" => !udp,
+    "udp" => udp,
+    "any" | "qualquer" => true,
+    _ => false,
+  };
+  rule.enabled && rule.inbound && protocol_matches && port_spec_covers(&rule.local_ports, port)
+}
+
+// windows firewall lets a matching block rule win over any allow rule, so the
+// block rules streamingGate leaves behind after closing count against the check
+fn diagnose_firewall(rules: Option<&[FirewallRule]>, ports: &[(bool, u16)]) -> DiagnosticItem {
+  let Some(rules) = rules else {
+    return diagnostic_item(
+      "firewall",
+      "warn",
+      Some("nao foi possivel ler as regras do firewall".to_string()),
+    );
+  };
+  let mut blocking: Vec<String> = Vec::new();
+  let mut uncovered: Vec<String> = Vec::new();
+  for (udp, port) in ports {
+    let applying: Vec<&FirewallRule> = rules
+      .iter()
+      .filter(|rule| rule_applies(rule, *udp, *port))
+      .collect();
+    for rule in applying.iter().filter(|rule| !rule.allow) {
+      if !blocking.contains(&rule.name) {
+        blocking.push(rule.name.clone());
+      }
+    }
+    if !applying.iter().any(|rule| rule.allow) {
+      uncovered.push(format_stream_port(*udp, *port));
+    }
+  }
+  if !blocking.is_empty() {
+    diagnostic_item(
+      "firewall",
+      "warn",
+      Some(format!("portas bloqueadas por: {}", blocking.join(", "))),
+    )
+  } else if !uncovered.is_empty() {
+    diagnostic_item(
+      "firewall",
+      "warn",
+      Some(format!("sem regra de permissao: {}", uncovered.join(", "))),
+    )
+  } else {
+    diagnostic_item("firewall", "pass", None)
+  }
+}
+
+fn diagnose_tool_errors(errors: &[ToolStatus]) -> DiagnosticItem {
+  let messages: Vec<String> = errors
+    .iter()
+    .filter_map(|error| error.detail.clone())
+    .collect();
+  if messages.is_empty() {
+    diagnostic_item("tool_errors", "pass", None)
+  } else {
+    diagnostic_item("tool_errors", "warn", Some(messages.join("; ")))
+  }
+}
+
+fn diagnose_elevation(elevated: bool) -> DiagnosticItem {
+  if elevated {
+    diagnostic_item("elevation", "pass", None)
+  } else {
+    diagnostic_item(
+      "elevation",
+      "warn",
+      Some("sem privilegios de administrador".to_string()),
+    )
+  }
+}
+
+fn diagnose_udp_loopback(result: Result<(), String>) -> DiagnosticItem {
+  match result {
+    Ok(()) => diagnostic_item("udp_loopback", "pass", None),
+    Err(error) => diagnostic_item("udp_loopback", "fail", Some(error)),
+  }
+}
+
+fn compose_diagnostics(items: Vec<DiagnosticItem>) -> DiagnosticsReport {
+  let overall = if items.iter().any(|item| item.status == "fail") {
+    "fail"
+  } else if items.iter().any(|item| item.status == "warn") {
+    "warn"
+  } else {
+    "pass"
+  };
+  DiagnosticsReport {
+    overall: overall.to_string(),
+    items,
+  }
+}
+
+fn read_firewall_rules() -> Option<String> {
+  let output = std::process::Command::new("netsh")
+    .args(["advfirewall", "firewall", "show", "rule", "name=all"])
+    .output()
+    .map_err(|error| log_tool_error("netsh", &error))
+    .ok()?;
+  if !output.status.success() {
+    return None;
+  }
+  Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn udp_loopback_self_test() -> Result<(), String> {
+  let receiver = std::net::UdpSocket::bind("127.0.0.1:0").map_err(|error| error.to_string())?;
+  receiver
+    .set_read_timeout(Some(std::time::Duration::from_millis(500)))
+    .map_err(|error| error.to_string())?;
+  let target = receiver.local_addr().map_err(|error| error.to_string())?;
+  let sender = std::net::UdpSocket::bind("127.0.0.1:0").map_err(|error| error.to_string())?;
+  let probe = b"opendesk-diagnostic";
+  sender.send_to(probe, target).map_err(|error| error.to_string())?;
+  let mut buffer = [0u8; 64];
+  let (size, _) = receiver
+    .recv_from(&mut buffer)
+    .map_err(|error| error.to_string())?;
+  if &buffer[..size] != probe {
+    return Err("pacote de teste corrompido".to_string());
+  }
+  Ok(())
+}
+
+fn collect_streaming_diagnostics() -> DiagnosticsReport {
+  let ports: Vec<(bool, PortStatus)> = sunshine_stream_ports()
+    .into_iter()
+    .map(|(udp, port)| (udp, check_port_available(port, udp)))
+    .collect();
+  let sunshine_owned = if cfg!(windows) {
+    sunshine_owned_ports()
+  } else {
+    HashSet::new()
+  };
+  let firewall_rules = if cfg!(windows) {
+    read_firewall_rules().map(|text| parse_netsh_rules(&text))
+  } else {
+    None
+  };
+  compose_diagnostics(vec![
+    diagnose_system_tools(&probe_system_tools()),
+    diagnose_firewall(firewall_rules.as_deref(), &sunshine_stream_ports()),
+    diagnose_ports(&ports, &sunshine_owned),
+    diagnose_local_ip(&list_ip_candidates_with_scores()),
+    diagnose_elevation(process_is_elevated()),
+    diagnose_udp_loopback(udp_loopback_self_test()),
+    // last, so failures from the checks above are included
+    diagnose_tool_errors(&get_tool_errors()),
+  ])
+}
+
+// netsh, wmic and powershell together take seconds; keep them off the main thread
+#[tauri::command]
+async fn run_streaming_diagnostics() -> Result<DiagnosticsReport, String> {
+  tauri::async_runtime::spawn_blocking(collect_streaming_diagnostics)
+    .await
+    .map_err(|error| error.to_string())
+}
+
+fn main() {
+  tauri::Builder::default()
+    .plugin(tauri_plugin_shell::init())
+    .plugin(tauri_plugin_dialog::init())
+    .setup(|app| {
+      let open = MenuItem::with_id(app, "open", "Abrir OpenDesk", true, Option::<&str>::None)?;
+      let status = MenuItem::with_id(app, "status", "Status: --", false, Option::<&str>::None)?;
+      let end_session =
+        MenuItem::with_id(app, "end_session", "Encerrar sessao", true, Option::<&str>::None)?;
+      let quit = MenuItem::with_id(app, "quit", "Sair", true, Option::<&str>::None)?;
+      let menu = Menu::with_items(
+        app,
+        &[
+          &open,
+          &status,
+          &end_session,
+          &PredefinedMenuItem::separator(app)?,
+          &quit,
+        ],
+      )?;
+
+      let icon = app.default_window_icon().cloned();
+      let mut builder = TrayIconBuilder::with_id("main").menu(&menu);
+      if let Some(icon) = icon {
+        builder = builder.icon(icon);
+      }
+      builder
+        .on_menu_event(|app: &AppHandle, event: MenuEvent| match event.id().as_ref() {
+          "open" => {
+            if let Some(window) = app.get_webview_window("main") {
+              let _ = window.show();
+              let _ = window.set_focus();
+            }
+          }
+          "end_session" => {
+            let _ = app.emit(EVENT_TRAY_ACTION, "end_session");
+          }
+          "quit" => {
+            app.exit(0);
+          }
+          _ => {}
+        })
+        .on_tray_icon_event(|tray: &TrayIcon, event: TrayIconEvent| {
+          if let TrayIconEvent::DoubleClick { .. } = event {
+            if let Some(window) = tray.app_handle().get_webview_window("main") {
+              let _ = window.show();
+              let _ = window.set_focus();
+            }
+          }
+        })
+        .build(app)?;
+      Ok(())
+    })
+    .invoke_handler(tauri::generate_handler![
+      validate_exe_path,
+      is_process_running,
+      launch_exe,
+      launch_moonlight,
+      get_local_pc_id,
+      detect_local_ip,
+      detect_local_ips,
+      list_ip_candidates_with_scores,
+      list_network_interfaces,
+      get_hardware_profile,
+      get_cached_hardware_profile,
+      cancel_hardware_profile,
+      get_pending_hardware_cancels,
+      detect_sunshine_path,
+      detect_moonlight_path,
+      start_sunshine,
+      start_moonlight,
+      moonlight_list,
+      moonlight_list_apps,
+      moonlight_pair,
+      moonlight_stream,
+      get_cursor_position,
+      set_cursor_position,
+      get_mouse_settings,
+      set_mouse_settings,
+      restore_mouse_settings,
+      detect_conflicting_input_software,
+      pick_executable,
+      detect_display_scaling,
+      logical_to_physical_point,
+      check_ports_available,
+      get_foreground_window,
+      is_network_metered,
+      sample_gpu_telemetry,
+      detect_decoder_support,
+      check_system_tools,
+      get_tool_errors,
+      list_backend_events,
+      moonlight_known_hosts,
+      is_elevated,
+      relaunch_as_admin,
+      run_streaming_diagnostics
+    ])
+    .run(tauri::generate_context!())
+    .expect("error while running tauri application");
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn clamp_to_desktop_keeps_points_inside_the_virtual_desktop() {
+    // two monitors side by side, the left one at negative coordinates
+    let desktop = (-1920, 0, 3840, 1080);
+    assert_eq!(clamp_to_desktop(100, 200, desktop), (100, 200));
+    assert_eq!(clamp_to_desktop(-5000, -10, desktop), (-1920, 0));
+    assert_eq!(clamp_to_desktop(5000, 5000, desktop), (1919, 1079));
+  }
+
+  #[test]
+  fn clamp_to_desktop_ignores_an_empty_desktop() {
+    assert_eq!(clamp_to_desktop(42, -7, (0, 0, 0, 0)), (42, -7));
+  }
+
+  fn port_status(port: u16, available: bool) -> PortStatus {
+    PortStatus {
+      port,
+      available,
+      error: None,
+    }
+  }
+
+  #[test]
+  fn diagnose_ports_accepts_ports_held_by_sunshine() {
+    let statuses = vec![(false, port_status(47989, false)), (true, port_status(47998, false))];
+    let owned: HashSet<(bool, u16)> = [(false, 47989)].into_iter().collect();
+    let item = diagnose_ports(&statuses, &owned);
+    assert_eq!(item.status, "warn");
+    assert_eq!(item.detail.as_deref(), Some("em uso: udp/47998"));
+
+    let owned: HashSet<(bool, u16)> = [(false, 47989), (true, 47998)].into_iter().collect();
+    assert_eq!(diagnose_ports(&statuses, &owned).status, "pass");
+  }
+
+  #[test]
+  fn parse_netstat_port_owners_reads_tcp_and_udp_rows() {
+    let text = "\
+Active Connections
+
+  Proto  Local Address          Foreign Address        State           PID
+  TCP    0.0.0.0:47989          0.0.0.0:0              LISTENING       4242
+  UDP    [::]:47998             *:*                                    4242
+";
+    assert_eq!(
+      parse_netstat_port_owners(text),
+      vec![(false, 47989, 4242), (true, 47998, 4242)]
+    );
+  }
+
+  const NETSH_RULES: &str = "\
+Rule Name:                            OpenDesk Sunshine pc-1
+----------------------------------------------------------------------
+Enabled:                              Yes
+Direction:                            In
+Profiles:                             Domain,Private,Public
+LocalIP:                              Any
+RemoteIP:                             Any
+Protocol:                             TCP
+LocalPort:                            47984,47989,47990
+RemotePort:                           Any
+Action:                               Allow
+
+Rule Name:                            OpenDesk Sunshine pc-1
+----------------------------------------------------------------------
+Enabled:                              Yes
+Direction:                            In
+Protocol:                             UDP
+LocalPort:                            47998-48010
+Action:                               Allow
+";
+
+  #[test]
+  fn parse_netsh_rules_reads_each_rule_block() {
+    let rules = parse_netsh_rules(NETSH_RULES);
+    assert_eq!(rules.len(), 2);
+    assert!(rules.iter().all(|rule| rule.enabled && rule.inbound && rule.allow));
+    assert_eq!(rules[1].protocol, "udp");
+    assert_eq!(rules[1].local_ports, "47998-48010");
+  }
+
+  #[test]
+  fn diagnose_firewall_requires_allow_rules_for_every_port() {
+    let ports = sunshine_stream_ports();
+    let allowed = parse_netsh_rules(NETSH_RULES);
+    assert_eq!(diagnose_firewall(Some(allowed.as_slice()), &ports).status, "pass");
+
+    // closeStreamingGate swaps the same rule names to action=block
+    let blocked = parse_netsh_rules(&NETSH_RULES.replace("Allow", "Block"));
+    let item = diagnose_firewall(Some(blocked.as_slice()), &ports);
+    assert_eq!(item.status, "warn");
+    assert_eq!(item.detail.as_deref(), Some("portas bloqueadas por: OpenDesk Sunshine pc-1"));
+
+    let tcp_only = &allowed[..1];
+    let item = diagnose_firewall(Some(tcp_only), &[(false, 47990), (true, 48010)]);
+    assert_eq!(item.detail.as_deref(), Some("sem regra de permissao: udp/48010"));
+
+    assert_eq!(diagnose_firewall(None, &ports).status, "warn");
+  }
+
+  #[test]
+  fn parse_size_bytes_takes_the_last_number_in_either_column_order() {
+    assert_eq!(parse_size_bytes("Fixed hard disk media  1000202273280"), Some(1000202273280));
+    assert_eq!(parse_size_bytes("512105932800  SSD"), Some(512105932800));
+    assert_eq!(parse_size_bytes("Removable Media"), None);
+  }
+
+  #[test]
+  fn drives_from_records_reads_physical_disk_csv() {
+    let csv = "\
+\"Model\",\"MediaType\",\"BusType\",\"Size\",\"FreeSpace\"
+\"Samsung SSD 980 PRO 1TB\",\"SSD\",\"NVMe\",\"1000204886016\",\"536870912000\"
+\"WDC WD20EZRZ-00Z5HB0\",\"HDD\",\"SATA\",\"2000398934016\",\"\"
+\"Generic Card Reader\",\"Unspecified\",\"USB\",\"\",\"\"
+";
+    let drives = drives_from_records(&parse_csv_records(csv));
+    assert_eq!(drives.len(), 2);
+    assert_eq!(drives[0].mediaType.as_deref(), Some("SSD"));
+    assert_eq!(drives[0].busType.as_deref(), Some("NVMe"));
+    assert_eq!((drives[0].sizeGb, drives[0].freeGb), (932, Some(500)));
+    assert_eq!(drives[1].mediaType.as_deref(), Some("HDD"));
+    assert_eq!(drives[1].freeGb, None);
+  }
+
+  #[test]
+  fn parse_wmic_list_records_splits_on_blank_lines() {
+    let text = "\r\r\nMediaType=Fixed hard disk media\r\r\nModel=WDC WD20EZRZ\r\r\nSize=2000398934016\r\r\n\r\r\n\r\r\nMediaType=Removable Media\r\r\nModel=Card Reader\r\r\nSize=\r\r\n";
+    let records = parse_wmic_list_records(text);
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].get("model").map(String::as_str), Some("WDC WD20EZRZ"));
+    let drives = drives_from_records(&records);
+    assert_eq!(drives.len(), 1);
+    assert_eq!(drives[0].sizeGb, 1863);
+  }
+
+  #[test]
+  fn local_pc_id_base_prefers_uuid_and_serial_over_mac() {
+    let uuid = || Some("4C4C4544-0042-3510-8051-B4C04F564433".to_string());
+    let serial = || Some("7B5QV53".to_string());
+    let mac = || Some("00-1A-2B-3C-4D-5E".to_string());
+    let base = local_pc_id_base(uuid(), serial(), mac(), None);
+    assert_eq!(base, "4C4C4544-0042-3510-8051-B4C04F564433|7B5QV53");
+    // pinned: changing the base assembly or the hash changes every stored id
+    assert_eq!(fnv1a_hash(&base), "2ccd691bcf9aa7b2");
+    assert_eq!(
+      local_pc_id_base(None, serial(), mac(), Some("Intel(R) Core(TM) i7".to_string())),
+      "7B5QV53|Intel(R) Core(TM) i7"
+    );
+    assert_eq!(local_pc_id_base(None, None, mac(), None), "00-1A-2B-3C-4D-5E");
+  }
+
+  #[test]
+  fn parse_getmac_csv_skips_virtual_and_disabled_adapters() {
+    let text = "\
+\"vEthernet (Default Switch)\",\"Hyper-V Virtual Ethernet Adapter\",\"00-15-5D-01-02-03\",\"\\Device\\Tcpip_{A}\"
+\"VirtualBox Host-Only Network\",\"VirtualBox Host-Only Ethernet Adapter\",\"0A-00-27-00-00-05\",\"\\Device\\Tcpip_{B}\"
+\"Ethernet 2\",\"VMnet Adapter\",\"00-50-56-C0-00-08\",\"\\Device\\Tcpip_{C}\"
+\"Local Area Connection* 1\",\"Wi-Fi Direct Adapter\",\"1A-2B-3C-4D-5E-6F\",\"Media disconnected\"
+\"Bluetooth\",\"Bluetooth Device (PAN)\",\"N/A\",\"Disabled\"
+\"Wi-Fi\",\"Intel(R) Wi-Fi 6 AX201 160MHz\",\"F4-26-79-AA-BB-CC\",\"\\Device\\Tcpip_{D}\"
+\"Ethernet\",\"Realtek PCIe GbE Family Controller\",\"D8-BB-C1-11-22-33\",\"Media disconnected\"
+";
+    assert_eq!(parse_getmac_csv(text).as_deref(), Some("D8-BB-C1-11-22-33"));
+  }
+
+  #[test]
+  fn hash_pc_id_supports_fnv1a_and_sha256() {
+    let base = "00-1A-2B-3C-4D-5E";
+    assert_eq!(hash_pc_id(base, None).unwrap(), "136404311f788b8e");
+    assert_eq!(hash_pc_id(base, Some("FNV1A")).unwrap(), "136404311f788b8e");
+    assert_eq!(
+      hash_pc_id(base, Some("sha256")).unwrap(),
+      "b0f3c7e0c7a017fd8a70d7fa93b9344c27bf34e6f4e8bb9a6587a75867e1ee33"
+    );
+    assert!(hash_pc_id(base, Some("md5")).is_err());
+  }
+
+  #[test]
+  fn classify_tool_probe_separates_missing_from_failing_tools() {
+    let missing: std::io::Result<std::process::Output> =
+      Err(std::io::Error::from(std::io::ErrorKind::NotFound));
+    assert_eq!(classify_tool_probe(&missing), ("missing", None));
+    let denied: std::io::Result<std::process::Output> =
+      Err(std::io::Error::from(std::io::ErrorKind::PermissionDenied));
+    assert_eq!(classify_tool_probe(&denied).0, "error");
+  }
+
+  #[test]
+  fn log_tool_error_keeps_the_last_failure_per_tool() {
+    let tool = "opendesk-test-tool";
+    log_tool_error(tool, &std::io::Error::from(std::io::ErrorKind::PermissionDenied));
+    log_tool_error(tool, &std::io::Error::from(std::io::ErrorKind::NotFound));
+    let errors: Vec<ToolStatus> = get_tool_errors()
+      .into_iter()
+      .filter(|error| error.name == tool)
+      .collect();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].status, "missing");
+    assert_eq!(
+      errors[0].detail.as_deref(),
+      Some("opendesk-test-tool nao encontrado no PATH")
+    );
+    assert_eq!(diagnose_tool_errors(&errors).status, "warn");
+  }
+
+  #[test]
+  fn build_relaunch_parameters_quotes_each_argument_for_argv() {
+    let args = vec![
+      "--minimized".to_string(),
+      r"C:\Program Files\OpenDesk\config.json".to_string(),
+      "say \"hi\"".to_string(),
+      r"C:\trailing dir\".to_string(),
+      String::new(),
+    ];
+    assert_eq!(
+      build_relaunch_parameters(&args),
+      r#"--minimized "C:\Program Files\OpenDesk\config.json" "say \"hi\"" "C:\trailing dir\\" """#
+    );
+  }
+
+  #[cfg(not(windows))]
+  #[test]
+  fn process_is_elevated_is_false_off_windows() {
+    assert!(!process_is_elevated());
+  }
+
+  #[test]
+  fn shape_gpu_telemetry_keeps_partial_readings() {
+    let telemetry = shape_gpu_telemetry(Some(61), Some(1860), None, Some(34)).unwrap();
+    assert_eq!(telemetry.tempC, Some(61));
+    assert_eq!(telemetry.coreClockMhz, Some(1860));
+    assert_eq!(telemetry.memClockMhz, None);
+    assert_eq!(telemetry.utilizationPct, Some(34));
+    assert!(shape_gpu_telemetry(None, None, None, None).is_none());
+  }
+
+  struct CountingProbe {
+    calls: std::cell::Cell<u32>,
+    fail: bool,
+  }
+
+  impl CountingProbe {
+    fn new(fail: bool) -> Self {
+      CountingProbe {
+        calls: std::cell::Cell::new(0),
+        fail,
+      }
+    }
+  }
+
+  impl HardwareProbe for CountingProbe {
+    fn detect(&self) -> Result<HardwareProfile, String> {
+      self.calls.set(self.calls.get() + 1);
+      if self.fail {
+        return Err("cancelled".to_string());
+      }
+      Ok(HardwareProfile {
+        cpuName: format!("CPU {}", self.calls.get()),
+        cpuPhysicalCores: Some(8),
+        cpuLogicalThreads: Some(16),
+        ramGb: 32,
+        gpuName: "NVIDIA GeForce RTX 4070".to_string(),
+        gpus: vec!["NVIDIA GeForce RTX 4070".to_string()],
+        vramMb: Some(12288),
+        storageSummary: "SSD 1TB".to_string(),
+        drives: Vec::new(),
+        osName: Some("Windows 11 Pro (22631)".to_string()),
+        screenResolution: Some("2560x1440".to_string()),
+        refreshHz: Some(144),
+        hdrCapable: Some(false),
+      })
+    }
+  }
+
+  #[test]
+  fn hardware_profile_cache_serves_the_second_call_without_detecting() {
+    let cache = HardwareProfileCache::new();
+    let probe = CountingProbe::new(false);
+    assert!(cache.latest().is_none());
+    let first = cache.get_or_detect(false, Duration::from_secs(60), &probe).unwrap();
+    let second = cache.get_or_detect(false, Duration::from_secs(60), &probe).unwrap();
+    assert_eq!(probe.calls.get(), 1);
+    assert_eq!(first.cpuName, second.cpuName);
+    assert_eq!(cache.latest().map(|profile| profile.cpuName).as_deref(), Some("CPU 1"));
+  }
+
+  #[test]
+  fn hardware_profile_cache_honours_ttl_and_force() {
+    let cache = HardwareProfileCache::new();
+    let probe = CountingProbe::new(false);
+    let ttl = Duration::from_secs(60);
+    cache.get_or_detect(false, ttl, &probe).unwrap();
+
+    let forced = cache.get_or_detect(true, ttl, &probe).unwrap();
+    assert_eq!(probe.calls.get(), 2);
+    assert_eq!(forced.cpuName, "CPU 2");
+
+    // a zero ttl makes every entry stale
+    cache.get_or_detect(false, Duration::ZERO, &probe).unwrap();
+    assert_eq!(probe.calls.get(), 3);
+  }
+
+  #[test]
+  fn hardware_profile_cache_keeps_the_last_good_profile_on_failure() {
+    let cache = HardwareProfileCache::new();
+    cache
+      .get_or_detect(false, Duration::from_secs(60), &CountingProbe::new(false))
+      .unwrap();
+    let failing = CountingProbe::new(true);
+    let result = cache.get_or_detect(true, Duration::from_secs(60), &failing);
+    assert_eq!(result.err().as_deref(), Some("cancelled"));
+    assert_eq!(cache.latest().map(|profile| profile.cpuName).as_deref(), Some("CPU 1"));
+    assert!(cache.fresh(Duration::ZERO).is_none());
+  }
+
+  #[test]
+  fn query_output_lines_trims_wmic_padding_and_blank_rows() {
+    let wmic = "UUID                                  \r\r\n4C4C4544-0047-3510-8052-B4C04F4E3432  \r\r\n\r\r\n";
+    assert_eq!(
+      query_output_lines(wmic),
+      vec!["UUID".to_string(), "4C4C4544-0047-3510-8052-B4C04F4E3432".to_string()]
+    );
+    let cim = "\r\nNVIDIA GeForce RTX 3070\r\nIntel(R) UHD Graphics 630\r\n";
+    assert_eq!(
+      query_output_lines(cim),
+      vec!["NVIDIA GeForce RTX 3070".to_string(), "Intel(R) UHD Graphics 630".to_string()]
+    );
+  }
+
+  #[test]
+  fn first_wmi_value_skips_the_wmic_header() {
+    let wmic = query_output_lines("SerialNumber  \r\r\nCZC1234XYZ  \r\r\n");
+    assert_eq!(first_wmi_value(&wmic, "serial"), Some("CZC1234XYZ".to_string()));
+    let cim = query_output_lines("CZC1234XYZ\r\n");
+    assert_eq!(first_wmi_value(&cim, "serial"), Some("CZC1234XYZ".to_string()));
+    let header_only = query_output_lines("UUID  \r\r\n\r\r\n");
+    assert_eq!(first_wmi_value(&header_only, "uuid"), None);
+  }
+
+  #[test]
+  fn gpu_names_from_lines_drops_the_header_row() {
+    let wmic = query_output_lines("Name  \r\r\nNVIDIA GeForce RTX 3070  \r\r\nIntel(R) UHD Graphics 630  \r\r\n");
+    assert_eq!(
+      gpu_names_from_lines(wmic),
+      vec!["NVIDIA GeForce RTX 3070".to_string(), "Intel(R) UHD Graphics 630".to_string()]
+    );
+    let cim = query_output_lines("AMD Radeon RX 6800\r\n");
+    assert_eq!(gpu_names_from_lines(cim), vec!["AMD Radeon RX 6800".to_string()]);
+  }
+
+  #[test]
+  fn parse_moonlight_apps_reads_numbered_and_plain_lines() {
+    let parse = |line: &str| parse_moonlight_app_line(line).map(|app| (app.id, app.name));
+    assert_eq!(parse("1. Desktop"), Some((Some(1), "Desktop".to_string())));
+    assert_eq!(
+      parse("2) Steam Big Picture (running)"),
+      Some((Some(2), "Steam Big Picture".to_string()))
+    );
+    assert_eq!(parse("  Cyberpunk 2077  "), Some((None, "Cyberpunk 2077".to_string())));
+    assert_eq!(parse("   "), None);
+    assert_eq!(parse("Applications on GAMING-PC:"), None);
+
+    let stdout = "Applications on GAMING-PC:\r\n\r\n1. Desktop\r\n2) Steam Big Picture (running)\r\nCyberpunk 2077\r\n";
+    let apps: Vec<(Option<u32>, String)> = parse_moonlight_apps(stdout)
+      .into_iter()
+      .map(|app| (app.id, app.name))
+      .collect();
+    assert_eq!(
+      apps,
+      vec![
+        (Some(1), "Desktop".to_string()),
+        (Some(2), "Steam Big Picture".to_string()),
+        (None, "Cyberpunk 2077".to_string()),
+      ]
+    );
+  }
+
+
+  #[test]
+  fn network_cost_is_metered_maps_cost_types() {
+    assert_eq!(network_cost_is_metered("Fixed"), Some(true));
+    assert_eq!(network_cost_is_metered("Variable\r"), Some(true));
+    assert_eq!(network_cost_is_metered("Unrestricted"), Some(false));
+    assert_eq!(network_cost_is_metered("Unknown"), None);
+    assert_eq!(network_cost_is_metered(""), None);
+  }
+
+  #[test]
+  fn shape_foreground_window_trims_the_utf16_title() {
+    let title: Vec<u16> = "Cyberpunk 2077 \u{2014} Configura\u{e7}\u{f5}es \t ".encode_utf16().collect();
+    let window = shape_foreground_window(&title, 4242, None);
+    assert_eq!(window.title, "Cyberpunk 2077 \u{2014} Configura\u{e7}\u{f5}es");
+    assert_eq!(window.pid, 4242);
+    assert_eq!(window.processName, None);
+  }
+
+  #[test]
+  fn set_cancel_evicts_the_oldest_id_and_ignores_duplicates() {
+    let ids: Vec<String> = (0..=MAX_PENDING_CANCELS)
+      .map(|index| format!("cancel-test-{}", index))
+      .collect();
+    for id in &ids {
+      set_cancel(id);
+    }
+    let pending = || -> Vec<String> {
+      get_pending_hardware_cancels()
+        .into_iter()
+        .filter(|id| id.starts_with("cancel-test-"))
+        .collect()
+    };
+    assert!(!is_cancelled(&ids[0]));
+    assert_eq!(pending(), ids[1..].to_vec());
+
+    set_cancel(&ids[5]);
+    assert_eq!(pending(), ids[1..].to_vec());
+
+    for id in &ids {
+      clear_cancel(id);
+    }
+    assert!(pending().is_empty());
+  }
+
+  #[test]
+  fn check_port_available_reports_bound_ports() {
+    let listener = std::net::TcpListener::bind("0.0.0.0:0").unwrap();
+    let tcp_port = listener.local_addr().unwrap().port();
+    let status = check_port_available(tcp_port, false);
+    assert!(!status.available);
+    assert!(status.error.is_some());
+    drop(listener);
+    let status = check_port_available(tcp_port, false);
+    assert!(status.available);
+    assert!(status.error.is_none());
+
+    let socket = std::net::UdpSocket::bind("0.0.0.0:0").unwrap();
+    let udp_port = socket.local_addr().unwrap().port();
+    let status = check_port_available(udp_port, true);
+    assert!(!status.available);
+    assert!(status.error.is_some());
+    drop(socket);
+    assert!(check_port_available(udp_port, true).available);
+  }
+
+  #[test]
+  fn check_ports_available_rejects_unknown_protocols() {
+    assert!(check_ports_available(vec![47989], "sctp".to_string()).is_err());
+    assert!(check_ports_available(Vec::new(), " UDP ".to_string()).is_ok());
+  }
+
+  #[test]
+  fn is_likely_virtual_adapter_flags_hypervisor_and_vpn_adapters() {
+    for name in [
+      "Ethernet adapter vEthernet (WSL)",
+      "Ethernet adapter VirtualBox Host-Only Network",
+      "Unknown adapter Tailscale",
+    ] {
+      assert!(is_likely_virtual_adapter(name), "{}", name);
+    }
+    for name in ["Ethernet adapter Ethernet", "Wireless LAN adapter Wi-Fi"] {
+      assert!(!is_likely_virtual_adapter(name), "{}", name);
+    }
+  }
+
+  #[test]
+  fn parse_ipconfig_adapters_keeps_only_ipv4_address_lines() {
+    let text = "\
+Windows IP Configuration
+
+Ethernet adapter Ethernet:
+
+   Connection-specific DNS Suffix  . : lan
+   IPv4 Address. . . . . . . . . . . : 192.168.1.20(Preferred)
+   Subnet Mask . . . . . . . . . . . : 255.255.255.0
+   Default Gateway . . . . . . . . . : 192.168.1.1
+
+Ethernet adapter vEthernet (WSL):
+
+   Endereco IPv4. . . . . . . . . . . : 172.28.16.1
+   Mascara de Sub-rede . . . . . . . . : 255.255.240.0
+   Gateway Padrao. . . . . . . . . . . :
+";
+    assert_eq!(
+      parse_ipconfig_adapters(text),
+      vec![
+        ("Ethernet adapter Ethernet".to_string(), vec!["192.168.1.20".to_string()]),
+        ("Ethernet adapter vEthernet (WSL)".to_string(), vec!["172.28.16.1".to_string()]),
+      ]
+    );
+  }
+
+  #[test]
+  fn mouse_params_round_trip_through_enhance_pointer_precision() {
+    for enhance in [true, false] {
+      let raw = RawMouseSettings { speed: 10, params: encode_mouse_params(enhance) };
+      assert_eq!(decode_mouse_settings(raw).enhancePointerPrecision, enhance);
+    }
+    assert_eq!(encode_mouse_params(true), [6, 10, 1]);
+    assert_eq!(encode_mouse_params(false), [0, 0, 0]);
+    let custom = RawMouseSettings { speed: 7, params: [4, 12, 2] };
+    let settings = decode_mouse_settings(custom);
+    assert_eq!((settings.speed, settings.enhancePointerPrecision), (7, true));
+  }
+
+  #[test]
+  fn remember_first_backup_keeps_the_original_settings() {
+    let original = RawMouseSettings { speed: 10, params: [6, 10, 1] };
+    let changed = RawMouseSettings { speed: 4, params: [0, 0, 0] };
+    let mut slot: Option<RawMouseSettings> = None;
+    remember_first_backup(&mut slot, original);
+    remember_first_backup(&mut slot, changed);
+    let restored = slot.take().map(|raw| (raw.speed, raw.params));
+    assert_eq!(restored, Some((10, [6, 10, 1])));
+
+    remember_first_backup(&mut slot, changed);
+    assert_eq!(slot.map(|raw| raw.speed), Some(4));
+  }
+
+  #[test]
+  fn find_conflicting_software_matches_process_names_case_insensitively() {
+    let processes: Vec<String> = ["explorer.exe", "AnyDesk.exe", "chrome.exe", "PARSECD.EXE"]
+      .iter()
+      .map(|name| name.to_string())
+      .collect();
+    assert_eq!(
+      find_conflicting_software(&processes),
+      vec!["Parsec".to_string(), "AnyDesk".to_string()]
+    );
+    let clean: Vec<String> = vec!["explorer.exe".to_string(), "chrome.exe".to_string()];
+    assert!(find_conflicting_software(&clean).is_empty());
+  }
+
+  #[test]
+  fn validate_picked_executable_checks_the_selected_file() {
+    assert_eq!(validate_picked_executable(None), Ok(None));
+    let missing = std::env::temp_dir().join("opendesk-missing-picker-test.exe");
+    assert!(validate_picked_executable(Some(missing)).is_err());
+    let not_exe = std::env::temp_dir().join("opendesk-picker-test.txt");
+    std::fs::write(&not_exe, b"").unwrap();
+    assert!(validate_picked_executable(Some(not_exe.clone())).is_err());
+    let _ = std::fs::remove_file(&not_exe);
+  }
+
+  #[cfg(windows)]
+  #[test]
+  fn validate_picked_executable_accepts_an_existing_exe() {
+    let exe = std::env::temp_dir().join("opendesk-picker-test.exe");
+    std::fs::write(&exe, b"").unwrap();
+    let expected = exe.to_string_lossy().to_string();
+    assert_eq!(validate_picked_executable(Some(exe.clone())), Ok(Some(expected)));
+    let _ = std::fs::remove_file(&exe);
+  }
+
+  #[test]
+  fn logical_to_physical_scales_and_rounds() {
+    assert_eq!((logical_to_physical(100, 1.5), logical_to_physical(200, 1.5)), (150, 300));
+    assert_eq!(logical_to_physical(101, 1.5), 152);
+    assert_eq!(logical_to_physical(-3, 1.5), -5);
+    assert_eq!(logical_to_physical(333, 1.25), 416);
+    assert_eq!(logical_to_physical(640, 1.0), 640);
+  }
+
+  #[test]
+  fn backend_events_lists_every_emitted_event() {
+    let source = include_str!("main.rs");
+    let source = source.split("#[cfg(test)]\nmod tests").next().unwrap();
+    let registered: HashSet<&str> = BACKEND_EVENTS.iter().map(|(name, _, _)| *name).collect();
+    let mut emitted = 0;
+    for (index, _) in source.match_indices(".emit(") {
+      let rest = source[index + ".emit(".len()..].trim_start();
+      let token = rest.split(|ch| ch == ',' || ch == ')').next().unwrap().trim();
+      let name = if let Some(literal) = token.strip_prefix('"') {
+        literal.trim_end_matches('"').to_string()
+      } else {
+        let declaration = format!("const {}: &str = \"", token);
+        let start = source.find(&declaration).unwrap_or_else(|| panic!("{} is not a const", token));
+        let value = &source[start + declaration.len()..];
+        value[..value.find('"').unwrap()].to_string()
+      };
+      assert!(registered.contains(name.as_str()), "{} is missing from BACKEND_EVENTS", name);
+      emitted += 1;
+    }
+    assert!(emitted >= BACKEND_EVENTS.len());
+  }
+
+  #[test]
+  fn known_hosts_from_moonlight_ini_sample() {
+    let ini = "\
+[General]
+latestSupportedVersion-v1=6.1.0
+
+[hosts]
+1\\hostname=GAMING-PC
+1\\manualaddress=192.168.1.50
+1\\srvcert=@ByteArray(-----BEGIN CERTIFICATE-----\\nMIIC...)
+2\\hostname=LIVING-ROOM
+2\\localaddress=192.168.1.60
+2\\srvcert=@ByteArray()
+size=2
+";
+    let hosts: Vec<(String, Option<String>, bool)> = known_hosts_from_entries(parse_moonlight_ini(ini))
+      .into_iter()
+      .map(|host| (host.name, host.address, host.paired))
+      .collect();
+    assert_eq!(
+      hosts,
+      vec![
+        ("GAMING-PC".to_string(), Some("192.168.1.50".to_string()), true),
+        ("LIVING-ROOM".to_string(), Some("192.168.1.60".to_string()), false),
+      ]
+    );
+  }
+
+  #[test]
+  fn known_hosts_from_moonlight_registry_sample() {
+    let reg = "\
+HKEY_CURRENT_USER\\Software\\Moonlight Game Streaming Project\\Moonlight\\hosts
+    size    REG_DWORD    0x2
+
+HKEY_CURRENT_USER\\Software\\Moonlight Game Streaming Project\\Moonlight\\hosts\\1
+    hostname    REG_SZ    GAMING-PC
+    manualaddress    REG_SZ    192.168.1.50
+    srvcert    REG_BINARY    2D2D2D2D2D424547494E
+
+HKEY_CURRENT_USER\\Software\\Moonlight Game Streaming Project\\Moonlight\\hosts\\2
+    hostname    REG_SZ    LIVING-ROOM
+    remoteaddress    REG_SZ    203.0.113.7
+    srvcert    REG_BINARY
+";
+    let hosts: Vec<(String, Option<String>, bool)> = known_hosts_from_entries(parse_moonlight_registry(reg))
+      .into_iter()
+      .map(|host| (host.name, host.address, host.paired))
+      .collect();
+    assert_eq!(
+      hosts,
+      vec![
+        ("GAMING-PC".to_string(), Some("192.168.1.50".to_string()), true),
+        ("LIVING-ROOM".to_string(), Some("203.0.113.7".to_string()), false),
+      ]
+    );
+  }
+
+  #[test]
+  fn pick_best_gpu_prefers_dedicated_over_integrated_and_virtual() {
+    let intel = "Intel(R) UHD Graphics 630".to_string();
+    let nvidia = "NVIDIA GeForce RTX 3070".to_string();
+    let basic = "Microsoft Basic Display Adapter".to_string();
+    assert!(gpu_vendor_score(&nvidia) > gpu_vendor_score(&intel));
+    assert!(gpu_vendor_score(&intel) > gpu_vendor_score(&basic));
+    assert_eq!(pick_best_gpu(&[intel.clone(), nvidia.clone()]), Some(nvidia.clone()));
+    assert_eq!(pick_best_gpu(&[nvidia.clone(), intel.clone()]), Some(nvidia));
+    assert_eq!(pick_best_gpu(&[basic.clone(), intel.clone()]), Some(intel.clone()));
+    assert_eq!(pick_best_gpu(&[intel.clone(), basic]), Some(intel));
+    assert_eq!(pick_best_gpu(&[]), None);
+  }
+
+  #[test]
+  fn format_os_name_appends_the_build_once() {
+    assert_eq!(format_os_name(Some("Windows 11 Pro"), Some("22631")), "Windows 11 Pro (22631)");
+    assert_eq!(format_os_name(None, None), "Windows");
+    assert_eq!(format_os_name(Some("  "), Some("")), "Windows");
+    assert_eq!(format_os_name(None, Some("19045")), "Windows (19045)");
+    assert_eq!(
+      format_os_name(Some("Windows 10 Home 10.0.19045"), Some("19045")),
+      "Windows 10 Home 10.0.19045"
+    );
+  }
+
+  #[test]
+  fn display_mode_from_settings_ignores_default_rates_and_empty_sizes() {
+    let mode = display_mode_from_settings(2560, 1440, 144);
+    assert_eq!(mode.resolution.as_deref(), Some("2560x1440"));
+    assert_eq!(mode.refresh_hz, Some(144));
+    assert_eq!(display_mode_from_settings(1920, 1080, 0).refresh_hz, None);
+    assert_eq!(display_mode_from_settings(1920, 1080, 1).refresh_hz, None);
+    assert_eq!(display_mode_from_settings(0, 1080, 60).resolution, None);
+    assert_eq!(display_mode_from_settings(1920, 0, 60).resolution, None);
+  }
+
+  #[test]
+  fn probe_outbound_ip_reports_the_loopback_interface() {
+    let peer = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let target = peer.local_addr().unwrap().to_string();
+    let address = probe_outbound_ip("127.0.0.1:0", &target);
+    assert_eq!(address, Some(std::net::IpAddr::from([127, 0, 0, 1])));
+    assert_eq!(probe_outbound_ip("127.0.0.1:0", "not-an-address"), None);
+  }
+
+  #[test]
+  fn rank_local_ips_orders_ipconfig_addresses_by_score() {
+    let text = "\
+Ethernet adapter Ethernet:
+
+   Link-local IPv6 Address . . . . . : fe80::1c2b:3d4e:5f60:7182%12(Preferred)
+   IPv4 Address. . . . . . . . . . . : 192.168.1.20(Preferred)
+   Subnet Mask . . . . . . . . . . . : 255.255.255.0
+   Default Gateway . . . . . . . . . : fe80::1%12
+                                       192.168.1.1
+
+Unknown adapter Tailscale:
+
+   IPv6 Address. . . . . . . . . . . : fd7a:115c:a1e0::1234(Preferred)
+   IPv4 Address. . . . . . . . . . . : 100.101.102.103(Preferred)
+   IPv4 Address. . . . . . . . . . . : 192.168.1.20(Duplicate)
+";
+    let addresses = ipconfig_addresses(text);
+    assert_eq!(addresses.len(), 5);
+    let ranked: Vec<(String, String, i32)> = rank_local_ips(addresses)
+      .into_iter()
+      .map(|entry| (entry.ip, entry.family, entry.score))
+      .collect();
+    assert_eq!(
+      ranked,
+      vec![
+        ("fd7a:115c:a1e0::1234".to_string(), "ipv6".to_string(), 3),
+        ("100.101.102.103".to_string(), "ipv4".to_string(), 3),
+        ("192.168.1.20".to_string(), "ipv4".to_string(), 2),
+        ("fe80::1c2b:3d4e:5f60:7182".to_string(), "ipv6".to_string(), 0),
+      ]
+    );
+  }
+
+  #[test]
+  fn decoder_hint_follows_the_gpu_architecture() {
+    let cases = [
+      ("NVIDIA GeForce RTX 4070 Ti", (true, true, true, "high")),
+      ("NVIDIA GeForce RTX 3060 Laptop GPU", (true, true, true, "high")),
+      ("NVIDIA GeForce RTX 2080 SUPER", (true, true, false, "high")),
+      ("NVIDIA GeForce GTX 1660 Ti", (true, true, false, "high")),
+      ("NVIDIA Quadro RTX 5000", (true, true, false, "high")),
+      ("NVIDIA Quadro RTX 3000", (true, true, false, "high")),
+      ("NVIDIA RTX 2000 Ada Generation", (true, true, true, "high")),
+      ("NVIDIA RTX A4000", (true, true, true, "high")),
+      ("AMD Radeon RX 6500 XT", (true, true, false, "high")),
+      ("AMD Radeon RX 6400", (true, true, false, "high")),
+      ("AMD Radeon RX 6700 XT", (true, true, true, "high")),
+      ("AMD Radeon RX 5700 XT", (true, true, false, "high")),
+      ("Radeon RX 580 Series", (true, true, false, "high")),
+      ("AMD Radeon RX 7900 XTX", (true, true, true, "high")),
+      ("Intel(R) Arc(TM) A770 Graphics", (true, true, true, "high")),
+      ("Intel(R) Iris(R) Xe Graphics", (true, true, true, "high")),
+      ("Intel(R) UHD Graphics 770", (true, true, true, "high")),
+      ("Intel(R) UHD Graphics 630", (true, true, false, "high")),
+      ("AMD Radeon(TM) Graphics", (true, true, false, "low")),
+      ("Microsoft Basic Display Adapter", (false, false, false, "unknown")),
+    ];
+    for (name, expected) in cases {
+      assert_eq!(decoder_hint(name), expected, "{}", name);
+    }
+  }
+
+  #[test]
+  fn compose_diagnostics_reports_the_worst_item() {
+    let tools = vec![ToolStatus {
+      name: "wmic".to_string(),
+      status: "missing".to_string(),
+      detail: None,
+    }];
+    let report = compose_diagnostics(vec![
+      diagnose_system_tools(&tools),
+      diagnose_ports(&[(false, port_status(47984, true))], &HashSet::new()),
+      diagnose_elevation(true),
+      diagnose_udp_loopback(Ok(())),
+    ]);
+    assert_eq!(report.overall, "warn");
+    let statuses: Vec<(&str, &str)> = report
+      .items
+      .iter()
+      .map(|item| (item.id.as_str(), item.status.as_str()))
+      .collect();
+    assert_eq!(
+      statuses,
+      vec![
+        ("system_tools", "warn"),
+        ("ports", "pass"),
+        ("elevation", "pass"),
+        ("udp_loopback", "pass"),
+      ]
+    );
+
+    let failed = compose_diagnostics(vec![
+      diagnose_elevation(true),
+      diagnose_udp_loopback(Err("timeout".to_string())),
+    ]);
+    assert_eq!(failed.overall, "fail");
+    assert_eq!(failed.items[1].detail.as_deref(), Some("timeout"));
+
+    assert_eq!(compose_diagnostics(vec![diagnose_elevation(true)]).overall, "pass");
+  }
 }
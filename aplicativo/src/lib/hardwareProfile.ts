@@ -1,14 +1,36 @@
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 
 import { isTauriRuntime } from './hostDaemon';
 
+export type DriveInfo = {
+  model: string;
+  mediaType?: string | null;
+  busType?: string | null;
+  sizeGb: number;
+  freeGb?: number | null;
+};
+
 export type HardwareProfile = {
   cpuName: string;
+  cpuPhysicalCores?: number;
+  cpuLogicalThreads?: number;
   ramGb: number;
   gpuName: string;
+  gpus?: string[];
+  vramMb?: number;
   storageSummary: string;
+  drives?: DriveInfo[];
   osName?: string;
   screenResolution?: string;
+  refreshHz?: number;
+  hdrCapable?: boolean;
+};
+
+type HardwareProfileReady = {
+  requestId: string;
+  profile: HardwareProfile | null;
+  error: string | null;
 };
 
 export async function getLocalMachineId(): Promise<string | null> {
@@ -24,11 +46,42 @@ export async function getLocalMachineId(): Promise<string | null> {
   }
 }
 
-export async function getHardwareProfile(requestId: string): Promise<HardwareProfile> {
+export async function getHardwareProfile(requestId: string, force = false): Promise<HardwareProfile> {
   if (!isTauriRuntime()) {
     throw new Error('Deteccao de hardware disponivel apenas no app desktop.');
   }
-  return invoke<HardwareProfile>('get_hardware_profile', { requestId });
+  let resolveReady: (profile: HardwareProfile) => void = () => undefined;
+  let rejectReady: (error: Error) => void = () => undefined;
+  const ready = new Promise<HardwareProfile>((resolve, reject) => {
+    resolveReady = resolve;
+    rejectReady = reject;
+  });
+  // subscribe before invoking so a cached profile emitted right away is not missed
+  const unlisten = await listen<HardwareProfileReady>('hardware-profile-ready', (event) => {
+    if (event.payload.requestId !== requestId) return;
+    if (event.payload.profile) {
+      resolveReady(event.payload.profile);
+    } else {
+      rejectReady(new Error(event.payload.error ?? 'Falha ao detectar hardware.'));
+    }
+  });
+  try {
+    await invoke('get_hardware_profile', { requestId, force });
+    return await ready;
+  } finally {
+    unlisten();
+  }
+}
+
+export async function getCachedHardwareProfile(): Promise<HardwareProfile | null> {
+  if (!isTauriRuntime()) return null;
+  try {
+    const profile = await invoke<HardwareProfile | null>('get_cached_hardware_profile');
+    return profile ?? null;
+  } catch (error) {
+    console.warn('[HARDWARE] cached profile fail', error);
+    return null;
+  }
 }
 
 export async function cancelHardwareProfile(requestId: string): Promise<void> {